@@ -0,0 +1,98 @@
+// Compares lookup throughput of the chained HashMap against the open-addressing OpenHashMap,
+// first with the default SipHash and then with FxHash.
+// Run with `cargo bench --bench hash_map`.
+use std::collections::hash_map::RandomState;
+use std::hash::BuildHasher;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use rust_algo_structures::data_structures::hash_map::hash_map::HashMap;
+use rust_algo_structures::data_structures::hasher::hasher::FxBuildHasher;
+use rust_algo_structures::data_structures::open_hash_map::open_hash_map::OpenHashMap;
+
+const ENTRIES: u64 = 100_000;
+const ROUNDS: u32 = 20;
+
+fn report(name: &str, elapsed: Duration, lookups: u64) {
+    let per_second = lookups as f64 / elapsed.as_secs_f64();
+    println!(
+        "{:<32} {:>10.2?} {:>8.1} M lookups/s",
+        name,
+        elapsed,
+        per_second / 1e6
+    );
+}
+
+// spreads the keys so they don't arrive in hash order
+fn key(i: u64) -> u64 {
+    i.wrapping_mul(0x9e37_79b9_7f4a_7c15)
+}
+
+fn bench_chained<S: BuildHasher>(hash_builder: S, label: &str) {
+    let mut map = HashMap::with_capacity_and_hasher(ENTRIES as usize, hash_builder);
+    for i in 0..ENTRIES {
+        map.insert(key(i), i);
+    }
+
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        for i in 0..ENTRIES {
+            black_box(map.search(black_box(&key(i))));
+        }
+    }
+    report(
+        &format!("chained hit ({label})"),
+        start.elapsed(),
+        ENTRIES * ROUNDS as u64,
+    );
+
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        for i in ENTRIES..2 * ENTRIES {
+            black_box(map.search(black_box(&key(i))));
+        }
+    }
+    report(
+        &format!("chained miss ({label})"),
+        start.elapsed(),
+        ENTRIES * ROUNDS as u64,
+    );
+}
+
+fn bench_open<S: BuildHasher>(hash_builder: S, label: &str) {
+    let mut map = OpenHashMap::with_hasher(hash_builder);
+    for i in 0..ENTRIES {
+        map.insert(key(i), i);
+    }
+
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        for i in 0..ENTRIES {
+            black_box(map.search(black_box(&key(i))));
+        }
+    }
+    report(
+        &format!("open addressing hit ({label})"),
+        start.elapsed(),
+        ENTRIES * ROUNDS as u64,
+    );
+
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        for i in ENTRIES..2 * ENTRIES {
+            black_box(map.search(black_box(&key(i))));
+        }
+    }
+    report(
+        &format!("open addressing miss ({label})"),
+        start.elapsed(),
+        ENTRIES * ROUNDS as u64,
+    );
+}
+
+fn main() {
+    bench_chained(RandomState::new(), "sip");
+    bench_open(RandomState::new(), "sip");
+    bench_chained(FxBuildHasher::default(), "fx");
+    bench_open(FxBuildHasher::default(), "fx");
+}
@@ -1,50 +1,162 @@
 pub mod b_tree_map {
+    use std::borrow::Borrow;
     use std::cmp::Ord;
+    use std::mem;
+    use std::ops::{Bound, RangeBounds};
 
     // Define the BTreeMap struct
+    // b is the minimum degree: every node except the root holds between b - 1 and 2b - 1 keys
     pub struct BTreeMap<K: Ord, V> {
         root: Option<Box<Node<K, V>>>,
         b: usize,
+        len: usize,
     }
 
     // Define the Node struct
+    // a leaf has no children, an internal node always has keys.len() + 1 children
     struct Node<K: Ord, V> {
         keys: Vec<K>,
         vals: Vec<V>,
+        #[allow(clippy::vec_box)]
         children: Vec<Box<Node<K, V>>>,
     }
 
     // Implement the new function for BTreeMap
     impl<K: Ord, V> BTreeMap<K, V> {
         pub fn new(b: usize) -> Self {
-            Self { root: None, b }
+            assert!(b >= 2, "branching factor must be at least 2");
+            Self {
+                root: None,
+                b,
+                len: 0,
+            }
         }
 
-        pub fn insert(&mut self, key: K, val: V) {
-            match self.root {
-                Some(ref mut root) => {
-                    root.insert(key, val);
-                }
-                None => {
-                    let mut root = Node::new();
-                    root.keys.push(key);
-                    root.vals.push(val);
-                    self.root = Some(Box::new(root));
-                }
+        // returns the number of pairs in the map
+        pub fn len(&self) -> usize {
+            self.len
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.len == 0
+        }
+
+        // inserts a key-value pair and returns the value previously stored under key, if any
+        pub fn insert(&mut self, key: K, val: V) -> Option<V> {
+            self.insert_slot(key, val).1
+        }
+
+        // inserts a key-value pair, splitting full nodes on the way down
+        // so that the leaf receiving the key always has room for it
+        // returns the slot now holding val together with the value it replaced
+        fn insert_slot(&mut self, key: K, val: V) -> (&mut V, Option<V>) {
+            let b = self.b;
+            let root = self.root.get_or_insert_with(|| Box::new(Node::new()));
+            if root.keys.len() == 2 * b - 1 {
+                let old_root = mem::replace(root, Box::new(Node::new()));
+                root.children.push(old_root);
+                root.split_child(0, b);
+            }
+            let (slot, old) = root.insert_non_full(key, val, b);
+            if old.is_none() {
+                self.len += 1;
             }
+            (slot, old)
         }
 
-        pub fn search(&self, key: &K) -> Option<&V> {
+        pub fn search<Q>(&self, key: &Q) -> Option<&V>
+        where
+            K: Borrow<Q>,
+            Q: Ord + ?Sized,
+        {
             match &self.root {
                 Some(root) => root.search(key),
                 None => None,
             }
         }
 
-        pub fn delete(&mut self, key: &K) {
-            if let Some(ref mut root) = self.root {
-                root.delete(key);
+        pub fn get<Q>(&self, key: &Q) -> Option<&V>
+        where
+            K: Borrow<Q>,
+            Q: Ord + ?Sized,
+        {
+            self.search(key)
+        }
+
+        pub fn contains_key<Q>(&self, key: &Q) -> bool
+        where
+            K: Borrow<Q>,
+            Q: Ord + ?Sized,
+        {
+            self.search(key).is_some()
+        }
+
+        // returns a mutable reference to the value stored under key
+        pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+        where
+            K: Borrow<Q>,
+            Q: Ord + ?Sized,
+        {
+            match &mut self.root {
+                Some(root) => root.search_mut(key).map(|(_, val)| val),
+                None => None,
+            }
+        }
+
+        // returns the entry for key, to be inspected or updated in place
+        pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+            if self.search(&key).is_none() {
+                return Entry::Vacant(VacantEntry { map: self, key });
             }
+            let (key, val) = self.root.as_mut().unwrap().search_mut(&key).unwrap();
+            Entry::Occupied(OccupiedEntry { key, val })
+        }
+
+        // removes a key, merging or rotating children on the way down so that
+        // every node we descend into can give up a key without underflowing
+        // returns the removed pair, or None if the key was not in the map
+        pub fn remove<Q>(&mut self, key: &Q) -> Option<(K, V)>
+        where
+            K: Borrow<Q>,
+            Q: Ord + ?Sized,
+        {
+            self.remove_from_root(|root, b| root.remove(key, b))
+        }
+
+        pub fn delete<Q>(&mut self, key: &Q)
+        where
+            K: Borrow<Q>,
+            Q: Ord + ?Sized,
+        {
+            self.remove(key);
+        }
+
+        // removes and returns the pair with the smallest key
+        pub fn pop_first(&mut self) -> Option<(K, V)> {
+            self.remove_from_root(|root, b| Some(root.remove_min(b)))
+        }
+
+        // removes and returns the pair with the largest key
+        pub fn pop_last(&mut self) -> Option<(K, V)> {
+            self.remove_from_root(|root, b| Some(root.remove_max(b)))
+        }
+
+        // runs a removal on the root and shrinks the tree if the root ran out of keys
+        fn remove_from_root<F>(&mut self, remove: F) -> Option<(K, V)>
+        where
+            F: FnOnce(&mut Node<K, V>, usize) -> Option<(K, V)>,
+        {
+            let b = self.b;
+            let root = self.root.as_mut()?;
+            let removed = remove(root, b);
+            if root.keys.is_empty() {
+                // the root ran out of keys: shrink the tree by one level
+                self.root = root.children.pop();
+            }
+            if removed.is_some() {
+                self.len -= 1;
+            }
+            removed
         }
 
         pub fn pre_order_traversal(&self) -> Vec<(&K, &V)> {
@@ -67,6 +179,97 @@ pub mod b_tree_map {
                 None => Vec::new(),
             }
         }
+
+        // returns the pair with the smallest key, or None if the map is empty
+        pub fn first_key_value(&self) -> Option<(&K, &V)> {
+            let mut node = self.root.as_deref()?;
+            while let Some(child) = node.children.first() {
+                node = child;
+            }
+            Some((&node.keys[0], &node.vals[0]))
+        }
+
+        // returns the pair with the largest key, or None if the map is empty
+        pub fn last_key_value(&self) -> Option<(&K, &V)> {
+            let mut node = self.root.as_deref()?;
+            while let Some(child) = node.children.last() {
+                node = child;
+            }
+            let last = node.keys.len() - 1;
+            Some((&node.keys[last], &node.vals[last]))
+        }
+
+        // returns a cursor on the first element above the bound
+        // Included(k) stops on the first key >= k, Excluded(k) on the first key > k
+        pub fn lower_bound<Q>(&self, bound: Bound<&Q>) -> Cursor<'_, K, V>
+        where
+            K: Borrow<Q>,
+            Q: Ord + ?Sized,
+        {
+            let mut cursor = Cursor::new(self.root.as_deref());
+            match bound {
+                Bound::Unbounded => cursor.move_next(),
+                Bound::Included(key) => cursor.seek_lower(key),
+                Bound::Excluded(key) => {
+                    cursor.seek_lower(key);
+                    if cursor.key().map(Borrow::borrow) == Some(key) {
+                        cursor.move_next();
+                    }
+                }
+            }
+            cursor
+        }
+
+        // returns a cursor on the last element below the bound
+        // Included(k) stops on the last key <= k, Excluded(k) on the last key < k
+        pub fn upper_bound<Q>(&self, bound: Bound<&Q>) -> Cursor<'_, K, V>
+        where
+            K: Borrow<Q>,
+            Q: Ord + ?Sized,
+        {
+            let mut cursor = Cursor::new(self.root.as_deref());
+            match bound {
+                Bound::Unbounded => cursor.move_prev(),
+                Bound::Included(key) => cursor.seek_upper(key),
+                Bound::Excluded(key) => {
+                    cursor.seek_upper(key);
+                    if cursor.key().map(Borrow::borrow) == Some(key) {
+                        cursor.move_prev();
+                    }
+                }
+            }
+            cursor
+        }
+
+        // returns a lazy double-ended iterator over the pairs whose keys fall in range, in key order
+        pub fn range<Q, R>(&self, range: R) -> Range<'_, K, V>
+        where
+            K: Borrow<Q>,
+            Q: Ord + ?Sized,
+            R: RangeBounds<Q>,
+        {
+            let front = self.lower_bound(range.start_bound());
+            let back = self.upper_bound(range.end_bound());
+            let finished = front.key().is_none() || back.key().is_none();
+            Range {
+                front,
+                back,
+                finished,
+            }
+        }
+
+        // returns a lazy double-ended iterator over all the pairs in key order
+        pub fn iter(&self) -> Range<'_, K, V> {
+            self.range::<K, _>(..)
+        }
+
+        pub fn keys(&self) -> impl DoubleEndedIterator<Item = &K> {
+            self.iter().map(|(key, _)| key)
+        }
+
+        pub fn values(&self) -> impl DoubleEndedIterator<Item = &V> {
+            self.iter().map(|(_, val)| val)
+        }
     }
 
     // Implement the new function for Node
@@ -79,14 +282,64 @@ pub mod b_tree_map {
             }
         }
 
-        fn insert(&mut self, key: K, val: V) {
-            let idx = self.keys.binary_search(&key).unwrap_or_else(|x| x);
-            self.keys.insert(idx, key);
-            self.vals.insert(idx, val);
+        fn is_leaf(&self) -> bool {
+            self.children.is_empty()
+        }
+
+        // splits the full child at index i around its median key,
+        // which moves up into this node
+        fn split_child(&mut self, i: usize, b: usize) {
+            let child = &mut self.children[i];
+            let mut sibling = Node::new();
+            sibling.keys = child.keys.split_off(b);
+            sibling.vals = child.vals.split_off(b);
+            if !child.is_leaf() {
+                sibling.children = child.children.split_off(b);
+            }
+            let median_key = child.keys.pop().unwrap();
+            let median_val = child.vals.pop().unwrap();
+
+            self.keys.insert(i, median_key);
+            self.vals.insert(i, median_val);
+            self.children.insert(i + 1, Box::new(sibling));
+        }
+
+        // inserts into a node that is known to have fewer than 2b - 1 keys
+        fn insert_non_full(&mut self, key: K, val: V, b: usize) -> (&mut V, Option<V>) {
+            let mut idx = match self.keys.binary_search(&key) {
+                Ok(idx) => {
+                    let old = mem::replace(&mut self.vals[idx], val);
+                    return (&mut self.vals[idx], Some(old));
+                }
+                Err(idx) => idx,
+            };
+
+            if self.is_leaf() {
+                self.keys.insert(idx, key);
+                self.vals.insert(idx, val);
+                return (&mut self.vals[idx], None);
+            }
+
+            if self.children[idx].keys.len() == 2 * b - 1 {
+                self.split_child(idx, b);
+                match key.cmp(&self.keys[idx]) {
+                    std::cmp::Ordering::Equal => {
+                        let old = mem::replace(&mut self.vals[idx], val);
+                        return (&mut self.vals[idx], Some(old));
+                    }
+                    std::cmp::Ordering::Greater => idx += 1,
+                    std::cmp::Ordering::Less => {}
+                }
+            }
+            self.children[idx].insert_non_full(key, val, b)
         }
 
-        fn search(&self, key: &K) -> Option<&V> {
-            match self.keys.binary_search(key) {
+        fn search<Q>(&self, key: &Q) -> Option<&V>
+        where
+            K: Borrow<Q>,
+            Q: Ord + ?Sized,
+        {
+            match self.keys.binary_search_by(|k| k.borrow().cmp(key)) {
                 Ok(idx) => Some(&self.vals[idx]),
                 Err(idx) => {
                     if idx < self.children.len() {
@@ -98,24 +351,146 @@ pub mod b_tree_map {
             }
         }
 
-        fn delete(&mut self, key: &K) {
-            if let Ok(idx) = self.keys.binary_search(key) {
-                self.keys.remove(idx);
-                self.vals.remove(idx);
-            } else {
-                for child in &mut self.children {
-                    child.delete(key);
+        fn search_mut<Q>(&mut self, key: &Q) -> Option<(&K, &mut V)>
+        where
+            K: Borrow<Q>,
+            Q: Ord + ?Sized,
+        {
+            match self.keys.binary_search_by(|k| k.borrow().cmp(key)) {
+                Ok(idx) => Some((&self.keys[idx], &mut self.vals[idx])),
+                Err(idx) => match self.children.get_mut(idx) {
+                    Some(child) => child.search_mut(key),
+                    None => None,
+                },
+            }
+        }
+
+        // removes key from the subtree rooted at this node and returns the pair
+        // the caller guarantees this node has at least b keys unless it is the root
+        fn remove<Q>(&mut self, key: &Q, b: usize) -> Option<(K, V)>
+        where
+            K: Borrow<Q>,
+            Q: Ord + ?Sized,
+        {
+            match self.keys.binary_search_by(|k| k.borrow().cmp(key)) {
+                Ok(idx) if self.is_leaf() => Some((self.keys.remove(idx), self.vals.remove(idx))),
+                Ok(idx) => {
+                    if self.children[idx].keys.len() >= b {
+                        // replace the key with its in-order predecessor
+                        let (k, v) = self.children[idx].remove_max(b);
+                        Some((
+                            mem::replace(&mut self.keys[idx], k),
+                            mem::replace(&mut self.vals[idx], v),
+                        ))
+                    } else if self.children[idx + 1].keys.len() >= b {
+                        // replace the key with its in-order successor
+                        let (k, v) = self.children[idx + 1].remove_min(b);
+                        Some((
+                            mem::replace(&mut self.keys[idx], k),
+                            mem::replace(&mut self.vals[idx], v),
+                        ))
+                    } else {
+                        // both neighbours are minimal: pull the key down into the merged child
+                        self.merge_children(idx);
+                        self.children[idx].remove(key, b)
+                    }
                 }
+                Err(_) if self.is_leaf() => None,
+                Err(idx) => {
+                    let idx = self.fill_child(idx, b);
+                    self.children[idx].remove(key, b)
+                }
+            }
+        }
+
+        // removes and returns the largest pair of this subtree
+        fn remove_max(&mut self, b: usize) -> (K, V) {
+            if self.is_leaf() {
+                return (self.keys.pop().unwrap(), self.vals.pop().unwrap());
+            }
+            let idx = self.fill_child(self.children.len() - 1, b);
+            self.children[idx].remove_max(b)
+        }
+
+        // removes and returns the smallest pair of this subtree
+        fn remove_min(&mut self, b: usize) -> (K, V) {
+            if self.is_leaf() {
+                return (self.keys.remove(0), self.vals.remove(0));
             }
+            let idx = self.fill_child(0, b);
+            self.children[idx].remove_min(b)
         }
 
+        // makes sure the child at index i has at least b keys before we descend into it,
+        // borrowing from a sibling when possible and merging otherwise
+        // returns the index the child ends up at
+        fn fill_child(&mut self, i: usize, b: usize) -> usize {
+            if self.children[i].keys.len() >= b {
+                i
+            } else if i > 0 && self.children[i - 1].keys.len() >= b {
+                self.borrow_from_prev(i);
+                i
+            } else if i < self.keys.len() && self.children[i + 1].keys.len() >= b {
+                self.borrow_from_next(i);
+                i
+            } else if i < self.keys.len() {
+                self.merge_children(i);
+                i
+            } else {
+                self.merge_children(i - 1);
+                i - 1
+            }
+        }
+
+        // rotates the last key of the left sibling through this node into child i
+        fn borrow_from_prev(&mut self, i: usize) {
+            let (left, right) = self.children.split_at_mut(i);
+            let sibling = &mut left[i - 1];
+            let child = &mut right[0];
+
+            let key = mem::replace(&mut self.keys[i - 1], sibling.keys.pop().unwrap());
+            let val = mem::replace(&mut self.vals[i - 1], sibling.vals.pop().unwrap());
+            child.keys.insert(0, key);
+            child.vals.insert(0, val);
+            if let Some(grandchild) = sibling.children.pop() {
+                child.children.insert(0, grandchild);
+            }
+        }
+
+        // rotates the first key of the right sibling through this node into child i
+        fn borrow_from_next(&mut self, i: usize) {
+            let (left, right) = self.children.split_at_mut(i + 1);
+            let child = &mut left[i];
+            let sibling = &mut right[0];
+
+            let key = mem::replace(&mut self.keys[i], sibling.keys.remove(0));
+            let val = mem::replace(&mut self.vals[i], sibling.vals.remove(0));
+            child.keys.push(key);
+            child.vals.push(val);
+            if !sibling.is_leaf() {
+                child.children.push(sibling.children.remove(0));
+            }
+        }
+
+        // merges child i + 1 and the separating key into child i
+        fn merge_children(&mut self, i: usize) {
+            let sibling = self.children.remove(i + 1);
+            let key = self.keys.remove(i);
+            let val = self.vals.remove(i);
+
+            let child = &mut self.children[i];
+            child.keys.push(key);
+            child.vals.push(val);
+            child.keys.extend(sibling.keys);
+            child.vals.extend(sibling.vals);
+            child.children.extend(sibling.children);
+        }
+
+        // visits the keys of a node before the keys of its children
         fn pre_order_traversal(&self) -> Vec<(&K, &V)> {
-            let mut result = Vec::new();
-            for i in 0..self.keys.len() {
-                result.push((&self.keys[i], &self.vals[i]));
-                if i < self.children.len() {
-                    result.extend(self.children[i].pre_order_traversal());
-                }
+            let mut result: Vec<(&K, &V)> = self.keys.iter().zip(self.vals.iter()).collect();
+            for child in &self.children {
+                result.extend(child.pre_order_traversal());
             }
             result
         }
@@ -134,18 +509,394 @@ pub mod b_tree_map {
             result
         }
 
+        // visits the keys of a node after the keys of all its children
         fn post_order_traversal(&self) -> Vec<(&K, &V)> {
             let mut result = Vec::new();
-            for i in 0..self.keys.len() {
-                if i < self.children.len() {
-                    result.extend(self.children[i].post_order_traversal());
+            for child in &self.children {
+                result.extend(child.post_order_traversal());
+            }
+            result.extend(self.keys.iter().zip(self.vals.iter()));
+            result
+        }
+    }
+
+    // A view into a single key of the map, which is either occupied or vacant
+    pub enum Entry<'a, K: Ord, V> {
+        Occupied(OccupiedEntry<'a, K, V>),
+        Vacant(VacantEntry<'a, K, V>),
+    }
+
+    pub struct OccupiedEntry<'a, K: Ord, V> {
+        key: &'a K,
+        val: &'a mut V,
+    }
+
+    pub struct VacantEntry<'a, K: Ord, V> {
+        map: &'a mut BTreeMap<K, V>,
+        key: K,
+    }
+
+    impl<'a, K: Ord, V> Entry<'a, K, V> {
+        pub fn key(&self) -> &K {
+            match self {
+                Entry::Occupied(entry) => entry.key(),
+                Entry::Vacant(entry) => entry.key(),
+            }
+        }
+
+        // returns the value for the key, inserting default first if the entry is vacant
+        pub fn or_insert(self, default: V) -> &'a mut V {
+            match self {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => entry.insert(default),
+            }
+        }
+
+        // like or_insert, but only builds the default value when the entry is vacant
+        pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
+            match self {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => entry.insert(default()),
+            }
+        }
+
+        pub fn or_default(self) -> &'a mut V
+        where
+            V: Default,
+        {
+            self.or_insert_with(V::default)
+        }
+
+        // runs f on the value if the entry is occupied, then hands the entry back
+        pub fn and_modify<F: FnOnce(&mut V)>(self, f: F) -> Self {
+            match self {
+                Entry::Occupied(entry) => {
+                    f(entry.val);
+                    Entry::Occupied(entry)
                 }
-                result.push((&self.keys[i], &self.vals[i]));
+                Entry::Vacant(entry) => Entry::Vacant(entry),
             }
-            if self.children.len() > self.keys.len() {
-                result.extend(self.children.last().unwrap().post_order_traversal());
+        }
+    }
+
+    impl<'a, K: Ord, V> OccupiedEntry<'a, K, V> {
+        pub fn key(&self) -> &K {
+            self.key
+        }
+
+        pub fn get(&self) -> &V {
+            self.val
+        }
+
+        pub fn get_mut(&mut self) -> &mut V {
+            self.val
+        }
+
+        // converts the entry into a mutable reference tied to the map's lifetime
+        pub fn into_mut(self) -> &'a mut V {
+            self.val
+        }
+
+        // replaces the value and returns the old one
+        pub fn insert(&mut self, val: V) -> V {
+            mem::replace(self.val, val)
+        }
+    }
+
+    impl<'a, K: Ord, V> VacantEntry<'a, K, V> {
+        pub fn key(&self) -> &K {
+            &self.key
+        }
+
+        pub fn into_key(self) -> K {
+            self.key
+        }
+
+        // inserts the value under the entry's key and returns a mutable reference to it
+        pub fn insert(self, val: V) -> &'a mut V {
+            self.map.insert_slot(self.key, val).0
+        }
+    }
+
+    // A read-only cursor pointing at one element of the map, or at the "ghost"
+    // position that sits between the last and the first element.
+    // Moving forward from the ghost lands on the first element and moving
+    // backward lands on the last, so a cursor can walk the map in both directions.
+    pub struct Cursor<'a, K: Ord, V> {
+        root: Option<&'a Node<K, V>>,
+        // path from the root: every entry but the last holds the index of the child
+        // we descended into, the last one holds the index of the current key
+        // an empty stack means the cursor is on the ghost position
+        stack: Vec<(&'a Node<K, V>, usize)>,
+    }
+
+    impl<'a, K: Ord, V> Cursor<'a, K, V> {
+        fn new(root: Option<&'a Node<K, V>>) -> Self {
+            Self {
+                root,
+                stack: Vec::new(),
+            }
+        }
+
+        pub fn key(&self) -> Option<&'a K> {
+            self.key_value().map(|(key, _)| key)
+        }
+
+        pub fn value(&self) -> Option<&'a V> {
+            self.key_value().map(|(_, val)| val)
+        }
+
+        pub fn key_value(&self) -> Option<(&'a K, &'a V)> {
+            let &(node, idx) = self.stack.last()?;
+            Some((&node.keys[idx], &node.vals[idx]))
+        }
+
+        // moves to the next element in key order
+        pub fn move_next(&mut self) {
+            match self.stack.last_mut() {
+                None => {
+                    if let Some(root) = self.root {
+                        self.descend_leftmost(root);
+                    }
+                }
+                Some((node, idx)) => {
+                    let node: &'a Node<K, V> = node;
+                    if node.is_leaf() {
+                        *idx += 1;
+                        if *idx == node.keys.len() {
+                            self.climb_forward();
+                        }
+                    } else {
+                        // the successor is the leftmost key of the right subtree
+                        *idx += 1;
+                        let child = &node.children[*idx];
+                        self.descend_leftmost(child);
+                    }
+                }
+            }
+        }
+
+        // moves to the previous element in key order
+        pub fn move_prev(&mut self) {
+            match self.stack.last_mut() {
+                None => {
+                    if let Some(root) = self.root {
+                        self.descend_rightmost(root);
+                    }
+                }
+                Some((node, idx)) => {
+                    let node: &'a Node<K, V> = node;
+                    if node.is_leaf() {
+                        if *idx == 0 {
+                            self.climb_backward();
+                        } else {
+                            *idx -= 1;
+                        }
+                    } else {
+                        // the predecessor is the rightmost key of the left subtree
+                        let child = &node.children[*idx];
+                        self.descend_rightmost(child);
+                    }
+                }
+            }
+        }
+
+        // returns the next element without moving the cursor
+        pub fn peek_next(&self) -> Option<(&'a K, &'a V)> {
+            let mut cursor = self.clone();
+            cursor.move_next();
+            cursor.key_value()
+        }
+
+        // returns the previous element without moving the cursor
+        pub fn peek_prev(&self) -> Option<(&'a K, &'a V)> {
+            let mut cursor = self.clone();
+            cursor.move_prev();
+            cursor.key_value()
+        }
+
+        fn descend_leftmost(&mut self, mut node: &'a Node<K, V>) {
+            self.stack.push((node, 0));
+            while let Some(child) = node.children.first() {
+                node = child;
+                self.stack.push((node, 0));
+            }
+        }
+
+        fn descend_rightmost(&mut self, mut node: &'a Node<K, V>) {
+            while let Some(child) = node.children.last() {
+                self.stack.push((node, node.children.len() - 1));
+                node = child;
+            }
+            self.stack.push((node, node.keys.len() - 1));
+        }
+
+        // leaves an exhausted leaf and stops on the first ancestor key after it
+        fn climb_forward(&mut self) {
+            self.stack.pop();
+            while let Some(&(node, idx)) = self.stack.last() {
+                if idx < node.keys.len() {
+                    return;
+                }
+                self.stack.pop();
+            }
+        }
+
+        // leaves an exhausted leaf and stops on the first ancestor key before it
+        fn climb_backward(&mut self) {
+            self.stack.pop();
+            while let Some((_, idx)) = self.stack.last_mut() {
+                if *idx > 0 {
+                    *idx -= 1;
+                    return;
+                }
+                self.stack.pop();
+            }
+        }
+
+        // positions the cursor on the first key >= key
+        fn seek_lower<Q>(&mut self, key: &Q)
+        where
+            K: Borrow<Q>,
+            Q: Ord + ?Sized,
+        {
+            let mut node = match self.root {
+                Some(root) => root,
+                None => return,
+            };
+            loop {
+                match node.keys.binary_search_by(|k| k.borrow().cmp(key)) {
+                    Ok(idx) => {
+                        self.stack.push((node, idx));
+                        return;
+                    }
+                    Err(idx) => {
+                        self.stack.push((node, idx));
+                        if node.is_leaf() {
+                            if idx == node.keys.len() {
+                                self.climb_forward();
+                            }
+                            return;
+                        }
+                        node = &node.children[idx];
+                    }
+                }
             }
-            result
+        }
+
+        // positions the cursor on the last key <= key
+        fn seek_upper<Q>(&mut self, key: &Q)
+        where
+            K: Borrow<Q>,
+            Q: Ord + ?Sized,
+        {
+            let mut node = match self.root {
+                Some(root) => root,
+                None => return,
+            };
+            loop {
+                match node.keys.binary_search_by(|k| k.borrow().cmp(key)) {
+                    Ok(idx) => {
+                        self.stack.push((node, idx));
+                        return;
+                    }
+                    Err(idx) => {
+                        self.stack.push((node, idx));
+                        if node.is_leaf() {
+                            self.climb_backward_from(idx);
+                            return;
+                        }
+                        node = &node.children[idx];
+                    }
+                }
+            }
+        }
+
+        // the top of the stack is a leaf and the target sits just before key idx
+        fn climb_backward_from(&mut self, idx: usize) {
+            if idx == 0 {
+                self.climb_backward();
+            } else if let Some((_, top)) = self.stack.last_mut() {
+                *top = idx - 1;
+            }
+        }
+    }
+
+    impl<K: Ord, V> Clone for Cursor<'_, K, V> {
+        fn clone(&self) -> Self {
+            Self {
+                root: self.root,
+                stack: self.stack.clone(),
+            }
+        }
+    }
+
+    // A lazy double-ended iterator over a range of the map, built from two cursors
+    // that walk towards each other until they cross.
+    pub struct Range<'a, K: Ord, V> {
+        front: Cursor<'a, K, V>,
+        back: Cursor<'a, K, V>,
+        finished: bool,
+    }
+
+    impl<'a, K: Ord, V> Iterator for Range<'a, K, V> {
+        type Item = (&'a K, &'a V);
+
+        fn next(&mut self) -> Option<Self::Item> {
+            if self.finished {
+                return None;
+            }
+            let (key, val) = self.front.key_value()?;
+            let back_key = self.back.key()?;
+            if key > back_key {
+                self.finished = true;
+                return None;
+            }
+            if key == back_key {
+                self.finished = true;
+            } else {
+                self.front.move_next();
+            }
+            Some((key, val))
+        }
+    }
+
+    impl<K: Ord, V> DoubleEndedIterator for Range<'_, K, V> {
+        fn next_back(&mut self) -> Option<Self::Item> {
+            if self.finished {
+                return None;
+            }
+            let (key, val) = self.back.key_value()?;
+            let front_key = self.front.key()?;
+            if key < front_key {
+                self.finished = true;
+                return None;
+            }
+            if key == front_key {
+                self.finished = true;
+            } else {
+                self.back.move_prev();
+            }
+            Some((key, val))
+        }
+    }
+
+    impl<K: Ord, V> Clone for Range<'_, K, V> {
+        fn clone(&self) -> Self {
+            Self {
+                front: self.front.clone(),
+                back: self.back.clone(),
+                finished: self.finished,
+            }
+        }
+    }
+
+    impl<'a, K: Ord, V> IntoIterator for &'a BTreeMap<K, V> {
+        type Item = (&'a K, &'a V);
+        type IntoIter = Range<'a, K, V>;
+
+        fn into_iter(self) -> Self::IntoIter {
+            self.iter()
         }
     }
 
@@ -153,11 +904,64 @@ pub mod b_tree_map {
     mod tests {
         use super::*;
 
+        // walks the whole tree checking the B-tree invariants and returns its height
+        fn check_invariants<K: Ord, V>(map: &BTreeMap<K, V>) -> usize {
+            fn check<K: Ord, V>(node: &Node<K, V>, b: usize, is_root: bool) -> usize {
+                assert!(node.keys.len() < 2 * b);
+                if !is_root {
+                    assert!(node.keys.len() >= b - 1);
+                }
+                assert_eq!(node.keys.len(), node.vals.len());
+                assert!(node.keys.windows(2).all(|w| w[0] < w[1]));
+                if node.is_leaf() {
+                    return 1;
+                }
+                assert_eq!(node.children.len(), node.keys.len() + 1);
+                for (i, key) in node.keys.iter().enumerate() {
+                    assert!(node.children[i].keys.last().unwrap() < key);
+                    assert!(node.children[i + 1].keys.first().unwrap() > key);
+                }
+                let height = check(&node.children[0], b, false);
+                for child in &node.children[1..] {
+                    assert_eq!(check(child, b, false), height);
+                }
+                height + 1
+            }
+
+            match &map.root {
+                Some(root) => {
+                    assert!(!root.keys.is_empty());
+                    check(root, map.b, true)
+                }
+                None => 0,
+            }
+        }
+
+        // deterministic permutation of 0..n so the tests don't need a rand dependency
+        fn shuffled(n: u64) -> Vec<u64> {
+            let mut keys: Vec<u64> = (0..n).collect();
+            let mut state = 0x2545_f491_4f6c_dd1d_u64;
+            for i in (1..keys.len()).rev() {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                keys.swap(i, (state % (i as u64 + 1)) as usize);
+            }
+            keys
+        }
+
         #[test]
         fn test_new() {
             let map: BTreeMap<i32, i32> = BTreeMap::new(2);
             assert!(map.root.is_none());
             assert_eq!(map.b, 2);
+            assert!(map.is_empty());
+        }
+
+        #[test]
+        #[should_panic(expected = "branching factor must be at least 2")]
+        fn test_new_invalid_branching_factor() {
+            let _map: BTreeMap<i32, i32> = BTreeMap::new(1);
         }
 
         #[test]
@@ -172,6 +976,84 @@ pub mod b_tree_map {
             assert_eq!(root.vals, vec![2, 4, 6]);
         }
 
+        #[test]
+        fn test_insert_splits_root() {
+            let mut map: BTreeMap<i32, i32> = BTreeMap::new(2);
+            for i in 1..=4 {
+                map.insert(i, i * 10);
+            }
+
+            let root = map.root.as_ref().unwrap();
+            assert_eq!(root.keys, vec![2]);
+            assert_eq!(root.children.len(), 2);
+            assert_eq!(root.children[0].keys, vec![1]);
+            assert_eq!(root.children[1].keys, vec![3, 4]);
+            assert_eq!(check_invariants(&map), 2);
+        }
+
+        #[test]
+        fn test_insert_existing_key_overwrites() {
+            let mut map: BTreeMap<i32, i32> = BTreeMap::new(2);
+            for i in 0..20 {
+                map.insert(i, i);
+            }
+            for i in 0..20 {
+                map.insert(i, i * 2);
+            }
+
+            assert_eq!(map.in_order_traversal().len(), 20);
+            for i in 0..20 {
+                assert_eq!(map.search(&i), Some(&(i * 2)));
+            }
+            check_invariants(&map);
+        }
+
+        #[test]
+        fn test_insert_returns_previous_value() {
+            let mut map: BTreeMap<i32, &str> = BTreeMap::new(2);
+            for i in 0..10 {
+                assert_eq!(map.insert(i, "first"), None);
+            }
+            assert_eq!(map.insert(4, "second"), Some("first"));
+            assert_eq!(map.insert(4, "third"), Some("second"));
+
+            assert_eq!(map.len(), 10);
+            assert_eq!(map.search(&4), Some(&"third"));
+        }
+
+        #[test]
+        fn test_get_mut() {
+            let mut map: BTreeMap<i32, i32> = BTreeMap::new(2);
+            for i in 0..50 {
+                map.insert(i, i);
+            }
+
+            for i in 0..50 {
+                *map.get_mut(&i).unwrap() += 100;
+            }
+            assert_eq!(map.get_mut(&50), None);
+            for i in 0..50 {
+                assert_eq!(map.search(&i), Some(&(i + 100)));
+            }
+        }
+
+        #[test]
+        fn test_insert_many_is_balanced() {
+            let mut map: BTreeMap<u64, u64> = BTreeMap::new(3);
+            for key in shuffled(10_000) {
+                map.insert(key, key + 1);
+            }
+
+            // with at least 3 children per internal node the height is bounded by log_3(n)
+            let height = check_invariants(&map);
+            assert!(height <= 9, "tree too tall: {}", height);
+            for key in 0..10_000 {
+                assert_eq!(map.search(&key), Some(&(key + 1)));
+            }
+            let keys: Vec<u64> = map.in_order_traversal().iter().map(|(k, _)| **k).collect();
+            assert_eq!(keys, (0..10_000).collect::<Vec<_>>());
+        }
+
         #[test]
         fn test_search() {
             let mut map: BTreeMap<i32, i32> = BTreeMap::new(2);
@@ -199,6 +1081,122 @@ pub mod b_tree_map {
             assert_eq!(map.search(&3), None);
         }
 
+        #[test]
+        fn test_remove_returns_pair() {
+            let mut map: BTreeMap<i32, String> = BTreeMap::new(2);
+            for i in 0..30 {
+                map.insert(i, i.to_string());
+            }
+
+            assert_eq!(map.remove(&7), Some((7, "7".to_string())));
+            assert_eq!(map.remove(&7), None);
+            assert_eq!(map.remove(&100), None);
+            assert_eq!(map.len(), 29);
+            check_invariants(&map);
+        }
+
+        #[test]
+        fn test_delete_rebalances() {
+            for b in 2..5 {
+                let mut map: BTreeMap<u64, u64> = BTreeMap::new(b);
+                for key in shuffled(2_000) {
+                    map.insert(key, key);
+                }
+
+                let order = shuffled(2_000);
+                for (removed, key) in order.iter().enumerate() {
+                    map.delete(key);
+                    assert_eq!(map.search(key), None);
+                    if removed % 97 == 0 {
+                        check_invariants(&map);
+                    }
+                }
+
+                assert!(map.root.is_none());
+                assert!(map.is_empty());
+            }
+        }
+
+        #[test]
+        fn test_delete_missing_key() {
+            let mut map: BTreeMap<i32, i32> = BTreeMap::new(2);
+            for i in (0..100).step_by(2) {
+                map.insert(i, i);
+            }
+            for i in (1..100).step_by(2) {
+                map.delete(&i);
+                check_invariants(&map);
+            }
+
+            assert_eq!(map.in_order_traversal().len(), 50);
+        }
+
+        #[test]
+        fn test_delete_shrinks_tree() {
+            let mut map: BTreeMap<i32, i32> = BTreeMap::new(2);
+            for i in 0..50 {
+                map.insert(i, i);
+            }
+            let height = check_invariants(&map);
+            for i in 0..45 {
+                map.delete(&i);
+            }
+
+            assert!(check_invariants(&map) < height);
+            let keys: Vec<i32> = map.in_order_traversal().iter().map(|(k, _)| **k).collect();
+            assert_eq!(keys, vec![45, 46, 47, 48, 49]);
+        }
+
+        #[test]
+        fn test_entry_counting() {
+            let mut map: BTreeMap<char, usize> = BTreeMap::new(2);
+            for c in "the quick brown fox jumps over the lazy dog".chars() {
+                *map.entry(c).or_insert(0) += 1;
+            }
+
+            assert_eq!(map.search(&'o'), Some(&4));
+            assert_eq!(map.search(&' '), Some(&8));
+            assert_eq!(map.search(&'z'), Some(&1));
+            assert_eq!(map.len(), 27);
+            check_invariants(&map);
+        }
+
+        #[test]
+        fn test_entry_variants() {
+            let mut map: BTreeMap<i32, Vec<i32>> = BTreeMap::new(2);
+
+            map.entry(1)
+                .or_insert_with(|| Vec::with_capacity(2))
+                .push(10);
+            map.entry(1).or_insert_with(|| unreachable!()).push(11);
+            map.entry(2).or_default().push(20);
+            assert_eq!(map.search(&1), Some(&vec![10, 11]));
+            assert_eq!(map.search(&2), Some(&vec![20]));
+
+            map.entry(1).and_modify(|v| v.clear()).or_default();
+            map.entry(3).and_modify(|v| v.push(0)).or_default();
+            assert_eq!(map.search(&1), Some(&vec![]));
+            assert_eq!(map.search(&3), Some(&vec![]));
+
+            match map.entry(2) {
+                Entry::Occupied(mut entry) => {
+                    assert_eq!(entry.key(), &2);
+                    assert_eq!(entry.insert(vec![21]), vec![20]);
+                    assert_eq!(entry.get(), &vec![21]);
+                }
+                Entry::Vacant(_) => panic!("key 2 should be occupied"),
+            }
+            match map.entry(4) {
+                Entry::Occupied(_) => panic!("key 4 should be vacant"),
+                Entry::Vacant(entry) => {
+                    assert_eq!(entry.key(), &4);
+                    entry.insert(vec![40]);
+                }
+            }
+            assert_eq!(map.search(&4), Some(&vec![40]));
+            assert_eq!(map.len(), 4);
+        }
+
         #[test]
         fn test_traversal() {
             let mut map: BTreeMap<i32, i32> = BTreeMap::new(2);
@@ -216,5 +1214,234 @@ pub mod b_tree_map {
                 vec![(&1, &2), (&3, &4), (&5, &6)]
             );
         }
+
+        #[test]
+        fn test_traversal_multi_level() {
+            let mut map: BTreeMap<i32, i32> = BTreeMap::new(2);
+            for i in 1..=4 {
+                map.insert(i, i);
+            }
+
+            // root [2] with children [1] and [3, 4]
+            let keys =
+                |pairs: Vec<(&i32, &i32)>| pairs.iter().map(|(k, _)| **k).collect::<Vec<_>>();
+            assert_eq!(keys(map.pre_order_traversal()), vec![2, 1, 3, 4]);
+            assert_eq!(keys(map.in_order_traversal()), vec![1, 2, 3, 4]);
+            assert_eq!(keys(map.post_order_traversal()), vec![1, 3, 4, 2]);
+        }
+
+        #[test]
+        fn test_first_and_last_key_value() {
+            let mut map: BTreeMap<u64, u64> = BTreeMap::new(2);
+            assert_eq!(map.first_key_value(), None);
+            assert_eq!(map.last_key_value(), None);
+
+            for key in shuffled(500) {
+                map.insert(key, key * 2);
+            }
+            assert_eq!(map.first_key_value(), Some((&0, &0)));
+            assert_eq!(map.last_key_value(), Some((&499, &998)));
+        }
+
+        #[test]
+        fn test_iter_is_lazy_and_double_ended() {
+            let mut map: BTreeMap<u64, u64> = BTreeMap::new(2);
+            for key in shuffled(1_000) {
+                map.insert(key, key);
+            }
+
+            let forward: Vec<u64> = map.keys().copied().collect();
+            assert_eq!(forward, (0..1_000).collect::<Vec<_>>());
+            let backward: Vec<u64> = map.keys().rev().copied().collect();
+            assert_eq!(backward, (0..1_000).rev().collect::<Vec<_>>());
+
+            // alternating ends must meet in the middle without repeating an element
+            let mut iter = map.iter();
+            let mut seen = Vec::new();
+            while let Some((k, _)) = iter.next() {
+                seen.push(*k);
+                match iter.next_back() {
+                    Some((k, _)) => seen.push(*k),
+                    None => break,
+                }
+            }
+            seen.sort();
+            assert_eq!(seen, (0..1_000).collect::<Vec<_>>());
+
+            let mut count = 0;
+            for (key, val) in &map {
+                assert_eq!(key, val);
+                count += 1;
+            }
+            assert_eq!(count, 1_000);
+        }
+
+        #[test]
+        fn test_range_bounds() {
+            let mut map: BTreeMap<i32, i32> = BTreeMap::new(2);
+            for i in (0..100).step_by(2) {
+                map.insert(i, i);
+            }
+            let keys = |range: Range<'_, i32, i32>| range.map(|(k, _)| *k).collect::<Vec<_>>();
+
+            assert_eq!(keys(map.range(10..16)), vec![10, 12, 14]);
+            assert_eq!(keys(map.range(10..=16)), vec![10, 12, 14, 16]);
+            assert_eq!(keys(map.range(11..17)), vec![12, 14, 16]);
+            assert_eq!(keys(map.range(..5)), vec![0, 2, 4]);
+            assert_eq!(keys(map.range(95..)), vec![96, 98]);
+            assert_eq!(keys(map.range(..)).len(), 50);
+            assert_eq!(
+                keys(map.range((Bound::Excluded(10), Bound::Excluded(16)))),
+                vec![12, 14]
+            );
+            assert_eq!(keys(map.range(11..12)), Vec::<i32>::new());
+            assert_eq!(keys(map.range(200..300)), Vec::<i32>::new());
+            assert_eq!(keys(map.range(-10..0)), Vec::<i32>::new());
+            assert_eq!(
+                map.range(10..16).rev().map(|(k, _)| *k).collect::<Vec<_>>(),
+                vec![14, 12, 10]
+            );
+
+            let empty: BTreeMap<i32, i32> = BTreeMap::new(2);
+            assert_eq!(empty.range(..).next(), None);
+        }
+
+        #[test]
+        fn test_range_matches_std() {
+            let mut map: BTreeMap<u64, u64> = BTreeMap::new(3);
+            let mut reference = std::collections::BTreeMap::new();
+            for key in shuffled(3_000).into_iter().filter(|k| k % 3 != 0) {
+                map.insert(key, key);
+                reference.insert(key, key);
+            }
+
+            for (start, end) in shuffled(60)
+                .chunks(2)
+                .map(|c| (c[0].min(c[1]) * 50, c[0].max(c[1]) * 50 + 7))
+            {
+                let ours: Vec<_> = map.range(start..end).collect();
+                let theirs: Vec<_> = reference.range(start..end).collect();
+                assert_eq!(ours, theirs);
+                let ours: Vec<_> = map.range(start..=end).rev().collect();
+                let theirs: Vec<_> = reference.range(start..=end).rev().collect();
+                assert_eq!(ours, theirs);
+            }
+        }
+
+        #[test]
+        fn test_cursors() {
+            let mut map: BTreeMap<i32, i32> = BTreeMap::new(2);
+            for i in (0..50).step_by(5) {
+                map.insert(i, i * 10);
+            }
+
+            let mut cursor = map.lower_bound(Bound::Included(&12));
+            assert_eq!(cursor.key_value(), Some((&15, &150)));
+            assert_eq!(cursor.peek_prev(), Some((&10, &100)));
+            assert_eq!(cursor.peek_next(), Some((&20, &200)));
+            cursor.move_prev();
+            cursor.move_prev();
+            assert_eq!(cursor.key(), Some(&5));
+
+            let cursor = map.lower_bound(Bound::Excluded(&15));
+            assert_eq!(cursor.key(), Some(&20));
+            let cursor = map.lower_bound(Bound::Included(&15));
+            assert_eq!(cursor.key(), Some(&15));
+            let cursor = map.lower_bound(Bound::Unbounded);
+            assert_eq!(cursor.key(), Some(&0));
+            let cursor = map.lower_bound(Bound::Included(&46));
+            assert_eq!(cursor.key(), None);
+
+            let mut cursor = map.upper_bound(Bound::Included(&12));
+            assert_eq!(cursor.key(), Some(&10));
+            cursor.move_next();
+            assert_eq!(cursor.value(), Some(&150));
+            let cursor = map.upper_bound(Bound::Excluded(&10));
+            assert_eq!(cursor.key(), Some(&5));
+            let cursor = map.upper_bound(Bound::Unbounded);
+            assert_eq!(cursor.key(), Some(&45));
+            let cursor = map.upper_bound(Bound::Excluded(&0));
+            assert_eq!(cursor.key(), None);
+        }
+
+        #[test]
+        fn test_cursor_wraps_through_ghost() {
+            let mut map: BTreeMap<i32, i32> = BTreeMap::new(2);
+            for i in 0..30 {
+                map.insert(i, i);
+            }
+
+            let mut cursor = map.upper_bound(Bound::Unbounded);
+            cursor.move_next();
+            assert_eq!(cursor.key(), None);
+            cursor.move_next();
+            assert_eq!(cursor.key(), Some(&0));
+            cursor.move_prev();
+            assert_eq!(cursor.key(), None);
+            cursor.move_prev();
+            assert_eq!(cursor.key(), Some(&29));
+
+            // walking the cursor across every element visits them in order both ways
+            let mut cursor = map.lower_bound(Bound::Unbounded);
+            for i in 0..30 {
+                assert_eq!(cursor.key(), Some(&i));
+                cursor.move_next();
+            }
+            assert_eq!(cursor.key(), None);
+            cursor.move_prev();
+            for i in (0..30).rev() {
+                assert_eq!(cursor.key(), Some(&i));
+                cursor.move_prev();
+            }
+        }
+
+        #[test]
+        fn test_borrowed_lookups() {
+            let mut map: BTreeMap<String, usize> = BTreeMap::new(2);
+            for word in ["delta", "alpha", "echo", "charlie", "bravo"] {
+                map.insert(word.to_string(), word.len());
+            }
+
+            assert_eq!(map.get("alpha"), Some(&5));
+            assert_eq!(map.search("echo"), Some(&4));
+            assert!(map.contains_key("charlie"));
+            assert!(!map.contains_key("foxtrot"));
+            *map.get_mut("bravo").unwrap() += 1;
+            assert_eq!(map.get("bravo"), Some(&6));
+
+            let keys: Vec<&str> = map
+                .range::<str, _>((Bound::Included("b"), Bound::Excluded("d")))
+                .map(|(k, _)| k.as_str())
+                .collect();
+            assert_eq!(keys, vec!["bravo", "charlie"]);
+            assert_eq!(
+                map.lower_bound(Bound::Excluded("charlie"))
+                    .key()
+                    .map(String::as_str),
+                Some("delta")
+            );
+
+            assert_eq!(map.remove("delta"), Some(("delta".to_string(), 5)));
+            map.delete("echo");
+            assert_eq!(map.len(), 3);
+        }
+
+        #[test]
+        fn test_pop_first_and_last() {
+            let mut map: BTreeMap<u64, u64> = BTreeMap::new(2);
+            assert_eq!(map.pop_first(), None);
+            assert_eq!(map.pop_last(), None);
+            for key in shuffled(300) {
+                map.insert(key, key * 2);
+            }
+
+            for i in 0..100 {
+                assert_eq!(map.pop_first(), Some((i, i * 2)));
+                assert_eq!(map.pop_last(), Some((299 - i, (299 - i) * 2)));
+                check_invariants(&map);
+            }
+            assert_eq!(map.len(), 100);
+            assert_eq!(map.first_key_value(), Some((&100, &200)));
+        }
     }
 }
@@ -0,0 +1,302 @@
+pub mod b_tree_set {
+    use std::{borrow::Borrow, cmp::Ordering, fmt, iter::Peekable, ops::RangeBounds};
+
+    use crate::data_structures::b_tree_map::b_tree_map::BTreeMap;
+
+    // An ordered set stored as the keys of a BTreeMap<T, ()>
+    // b is the branching factor passed on to the underlying map
+    pub struct BTreeSet<T: Ord> {
+        map: BTreeMap<T, ()>,
+    }
+
+    impl<T: Ord> BTreeSet<T> {
+        pub fn new(b: usize) -> Self {
+            Self {
+                map: BTreeMap::new(b),
+            }
+        }
+
+        // adds value to the set and returns true if it was not already present
+        pub fn insert(&mut self, value: T) -> bool {
+            self.map.insert(value, ()).is_none()
+        }
+
+        pub fn contains<Q>(&self, value: &Q) -> bool
+        where
+            T: Borrow<Q>,
+            Q: Ord + ?Sized,
+        {
+            self.map.contains_key(value)
+        }
+
+        // removes value from the set and returns true if it was present
+        pub fn remove<Q>(&mut self, value: &Q) -> bool
+        where
+            T: Borrow<Q>,
+            Q: Ord + ?Sized,
+        {
+            self.map.remove(value).is_some()
+        }
+
+        pub fn len(&self) -> usize {
+            self.map.len()
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.map.is_empty()
+        }
+
+        // returns the smallest element
+        pub fn first(&self) -> Option<&T> {
+            self.map.first_key_value().map(|(value, _)| value)
+        }
+
+        // returns the largest element
+        pub fn last(&self) -> Option<&T> {
+            self.map.last_key_value().map(|(value, _)| value)
+        }
+
+        // removes and returns the smallest element
+        pub fn pop_first(&mut self) -> Option<T> {
+            self.map.pop_first().map(|(value, _)| value)
+        }
+
+        // removes and returns the largest element
+        pub fn pop_last(&mut self) -> Option<T> {
+            self.map.pop_last().map(|(value, _)| value)
+        }
+
+        // iterates over the elements in ascending order
+        pub fn iter(&self) -> impl DoubleEndedIterator<Item = &T> {
+            self.map.keys()
+        }
+
+        // iterates in ascending order over the elements that fall in range
+        pub fn range<Q, R>(&self, range: R) -> impl DoubleEndedIterator<Item = &T>
+        where
+            T: Borrow<Q>,
+            Q: Ord + ?Sized,
+            R: RangeBounds<Q>,
+        {
+            self.map.range(range).map(|(value, _)| value)
+        }
+
+        // lazily yields, in ascending order, every element that is in self or other
+        pub fn union<'a>(&'a self, other: &'a Self) -> impl Iterator<Item = &'a T> + 'a {
+            Union {
+                a: self.iter().peekable(),
+                b: other.iter().peekable(),
+            }
+        }
+
+        // lazily yields, in ascending order, the elements present in both sets
+        pub fn intersection<'a>(&'a self, other: &'a Self) -> impl Iterator<Item = &'a T> + 'a {
+            Intersection {
+                a: self.iter().peekable(),
+                b: other.iter().peekable(),
+            }
+        }
+
+        // lazily yields, in ascending order, the elements of self that are not in other
+        pub fn difference<'a>(&'a self, other: &'a Self) -> impl Iterator<Item = &'a T> + 'a {
+            Difference {
+                a: self.iter().peekable(),
+                b: other.iter().peekable(),
+            }
+        }
+    }
+
+    impl<T: Ord + fmt::Debug> fmt::Debug for BTreeSet<T> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_set().entries(self.iter()).finish()
+        }
+    }
+
+    impl<T: Ord> Extend<T> for BTreeSet<T> {
+        fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+            for value in iter {
+                self.insert(value);
+            }
+        }
+    }
+
+    // The set algebra iterators below walk both sorted sequences in lock-step,
+    // always advancing the side with the smaller element, so each one runs in
+    // O(n + m) without any lookups into the other set.
+
+    struct Union<'a, T: 'a, I: Iterator<Item = &'a T>> {
+        a: Peekable<I>,
+        b: Peekable<I>,
+    }
+
+    impl<'a, T: Ord + 'a, I: Iterator<Item = &'a T>> Iterator for Union<'a, T, I> {
+        type Item = &'a T;
+
+        fn next(&mut self) -> Option<&'a T> {
+            let order = match (self.a.peek(), self.b.peek()) {
+                (Some(x), Some(y)) => x.cmp(y),
+                (Some(_), None) => Ordering::Less,
+                (None, _) => Ordering::Greater,
+            };
+            match order {
+                Ordering::Less => self.a.next(),
+                Ordering::Greater => self.b.next(),
+                Ordering::Equal => {
+                    self.b.next();
+                    self.a.next()
+                }
+            }
+        }
+    }
+
+    struct Intersection<'a, T: 'a, I: Iterator<Item = &'a T>> {
+        a: Peekable<I>,
+        b: Peekable<I>,
+    }
+
+    impl<'a, T: Ord + 'a, I: Iterator<Item = &'a T>> Iterator for Intersection<'a, T, I> {
+        type Item = &'a T;
+
+        fn next(&mut self) -> Option<&'a T> {
+            loop {
+                let x = *self.a.peek()?;
+                let y = *self.b.peek()?;
+                match x.cmp(y) {
+                    Ordering::Less => {
+                        self.a.next();
+                    }
+                    Ordering::Greater => {
+                        self.b.next();
+                    }
+                    Ordering::Equal => {
+                        self.b.next();
+                        return self.a.next();
+                    }
+                }
+            }
+        }
+    }
+
+    struct Difference<'a, T: 'a, I: Iterator<Item = &'a T>> {
+        a: Peekable<I>,
+        b: Peekable<I>,
+    }
+
+    impl<'a, T: Ord + 'a, I: Iterator<Item = &'a T>> Iterator for Difference<'a, T, I> {
+        type Item = &'a T;
+
+        fn next(&mut self) -> Option<&'a T> {
+            loop {
+                let x = *self.a.peek()?;
+                let y = match self.b.peek() {
+                    Some(y) => *y,
+                    None => return self.a.next(),
+                };
+                match x.cmp(y) {
+                    Ordering::Less => return self.a.next(),
+                    Ordering::Greater => {
+                        self.b.next();
+                    }
+                    Ordering::Equal => {
+                        self.a.next();
+                        self.b.next();
+                    }
+                }
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn set_of(b: usize, values: &[i32]) -> BTreeSet<i32> {
+            let mut set = BTreeSet::new(b);
+            set.extend(values.iter().copied());
+            set
+        }
+
+        #[test]
+        fn test_insert_contains_remove() {
+            let mut set = BTreeSet::new(2);
+            assert!(set.insert(3));
+            assert!(set.insert(1));
+            assert!(!set.insert(3));
+            assert_eq!(set.len(), 2);
+
+            assert!(set.contains(&1));
+            assert!(!set.contains(&2));
+            assert!(set.remove(&1));
+            assert!(!set.remove(&1));
+            assert_eq!(set.len(), 1);
+        }
+
+        #[test]
+        fn test_ordered_iteration_and_range() {
+            let set = set_of(2, &[9, 3, 7, 1, 5, 11, 13]);
+
+            assert_eq!(
+                set.iter().copied().collect::<Vec<_>>(),
+                vec![1, 3, 5, 7, 9, 11, 13]
+            );
+            assert_eq!(
+                set.iter().rev().copied().collect::<Vec<_>>(),
+                vec![13, 11, 9, 7, 5, 3, 1]
+            );
+            assert_eq!(set.range(4..=9).copied().collect::<Vec<_>>(), vec![5, 7, 9]);
+            assert_eq!(format!("{:?}", set_of(2, &[2, 1])), "{1, 2}");
+        }
+
+        #[test]
+        fn test_first_last_and_pops() {
+            let mut set = set_of(3, &(0..100).collect::<Vec<_>>());
+            assert_eq!(set.first(), Some(&0));
+            assert_eq!(set.last(), Some(&99));
+
+            assert_eq!(set.pop_first(), Some(0));
+            assert_eq!(set.pop_last(), Some(99));
+            assert_eq!(set.first(), Some(&1));
+            assert_eq!(set.last(), Some(&98));
+            assert_eq!(set.len(), 98);
+
+            let mut empty: BTreeSet<i32> = BTreeSet::new(2);
+            assert_eq!(empty.first(), None);
+            assert_eq!(empty.pop_first(), None);
+            assert_eq!(empty.pop_last(), None);
+        }
+
+        #[test]
+        fn test_merge_set_algebra() {
+            let a = set_of(2, &[1, 3, 5, 7, 9, 10]);
+            let b = set_of(2, &[2, 3, 4, 9, 10, 12]);
+            let collect = |iter: &mut dyn Iterator<Item = &i32>| iter.copied().collect::<Vec<_>>();
+
+            assert_eq!(collect(&mut a.union(&b)), vec![1, 2, 3, 4, 5, 7, 9, 10, 12]);
+            assert_eq!(collect(&mut a.intersection(&b)), vec![3, 9, 10]);
+            assert_eq!(collect(&mut a.difference(&b)), vec![1, 5, 7]);
+            assert_eq!(collect(&mut b.difference(&a)), vec![2, 4, 12]);
+
+            let empty = BTreeSet::new(2);
+            assert_eq!(collect(&mut a.union(&empty)), vec![1, 3, 5, 7, 9, 10]);
+            assert_eq!(collect(&mut empty.union(&a)), vec![1, 3, 5, 7, 9, 10]);
+            assert_eq!(collect(&mut a.intersection(&empty)), Vec::<i32>::new());
+            assert_eq!(collect(&mut a.difference(&empty)), vec![1, 3, 5, 7, 9, 10]);
+        }
+
+        #[test]
+        fn test_merge_set_algebra_large() {
+            let evens = set_of(3, &(0..1_000).step_by(2).collect::<Vec<_>>());
+            let threes = set_of(4, &(0..1_000).step_by(3).collect::<Vec<_>>());
+
+            let sixes: Vec<i32> = evens.intersection(&threes).copied().collect();
+            assert_eq!(sixes, (0..1_000).step_by(6).collect::<Vec<_>>());
+            let union: Vec<i32> = evens.union(&threes).copied().collect();
+            assert_eq!(
+                union,
+                (0..1_000)
+                    .filter(|i| i % 2 == 0 || i % 3 == 0)
+                    .collect::<Vec<_>>()
+            );
+        }
+    }
+}
@@ -0,0 +1,777 @@
+pub mod cache {
+    use std::{
+        borrow::Borrow,
+        hash::{Hash, Hasher},
+        mem,
+        ptr::{self, NonNull},
+    };
+
+    use crate::data_structures::{
+        hash_map::hash_map::HashMap,
+        linked_list::linked_list::{LinkedList, NodeHandle},
+    };
+
+    // what a cache has been asked and what it had to throw away
+    // only get counts towards hits and misses, peek does not
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub struct CacheStats {
+        pub hits: u64,
+        pub misses: u64,
+        pub evictions: u64,
+    }
+
+    impl CacheStats {
+        // the fraction of gets that found their key, or 0 before the first get
+        pub fn hit_rate(&self) -> f64 {
+            let lookups = self.hits + self.misses;
+            if lookups == 0 {
+                0.0
+            } else {
+                self.hits as f64 / lookups as f64
+            }
+        }
+    }
+
+    // called with the key and value of every entry evicted to make room
+    // it has to be Send so that a cache can be shared between threads behind a Mutex
+    type EvictionCallback<K, V> = Box<dyn FnMut(K, V) + Send>;
+
+    // Both caches keep their (key, value) pairs in the crate's LinkedList, ordered from
+    // most to least recently used, and map every key to a handle on its node, so finding
+    // an entry and moving or removing it are all O(1). The key is only stored once, in
+    // the node: the map holds a KeyRef pointing at it, which stays valid because a node
+    // never moves while it is in a list, and which is removed before its node is freed.
+    struct KeyRef<K>(NonNull<K>);
+
+    impl<K> KeyRef<K> {
+        fn get(&self) -> &K {
+            unsafe { self.0.as_ref() }
+        }
+    }
+
+    impl<K: Hash> Hash for KeyRef<K> {
+        fn hash<H: Hasher>(&self, state: &mut H) {
+            self.get().hash(state);
+        }
+    }
+
+    impl<K: PartialEq> PartialEq for KeyRef<K> {
+        fn eq(&self, other: &Self) -> bool {
+            self.get() == other.get()
+        }
+    }
+
+    impl<K: Eq> Eq for KeyRef<K> {}
+
+    // A borrowed lookup key, like a &str for String keys. KeyRef<K> borrows as a
+    // KeyWrapper<Q> whenever K: Borrow<Q>, so the map can be searched with a &Q.
+    // Borrowing as Q directly would conflict with the blanket Borrow<T> for T.
+    #[repr(transparent)]
+    struct KeyWrapper<Q: ?Sized>(Q);
+
+    impl<Q: ?Sized> KeyWrapper<Q> {
+        fn from_ref(key: &Q) -> &Self {
+            // repr(transparent) gives KeyWrapper<Q> the same layout as Q
+            unsafe { &*(key as *const Q as *const KeyWrapper<Q>) }
+        }
+    }
+
+    impl<Q: Hash + ?Sized> Hash for KeyWrapper<Q> {
+        fn hash<H: Hasher>(&self, state: &mut H) {
+            self.0.hash(state);
+        }
+    }
+
+    impl<Q: PartialEq + ?Sized> PartialEq for KeyWrapper<Q> {
+        fn eq(&self, other: &Self) -> bool {
+            self.0 == other.0
+        }
+    }
+
+    impl<Q: Eq + ?Sized> Eq for KeyWrapper<Q> {}
+
+    impl<K: Borrow<Q>, Q: ?Sized> Borrow<KeyWrapper<Q>> for KeyRef<K> {
+        fn borrow(&self) -> &KeyWrapper<Q> {
+            KeyWrapper::from_ref(self.get().borrow())
+        }
+    }
+
+    // what the map of both caches points at, the node of an entry's (key, value) pair
+    type Handle<K, V> = NodeHandle<(K, V)>;
+
+    // pointers to the key and the value in the node of handle, which must be alive
+    // they are taken without creating a reference to the whole pair, so writing
+    // through one never invalidates a KeyRef made from the other
+    unsafe fn key_ptr<K, V>(handle: Handle<K, V>) -> NonNull<K> {
+        unsafe {
+            let pair = handle.element().as_ptr();
+            NonNull::new_unchecked(ptr::addr_of_mut!((*pair).0))
+        }
+    }
+
+    unsafe fn value_ptr<K, V>(handle: Handle<K, V>) -> *mut V {
+        unsafe { ptr::addr_of_mut!((*handle.element().as_ptr()).1) }
+    }
+
+    // A least recently used cache: once full, putting a new key evicts the entry
+    // that has gone the longest without a get or put.
+    pub struct LruCache<K, V> {
+        map: HashMap<KeyRef<K>, Handle<K, V>>,
+        // most recently used at the front
+        list: LinkedList<(K, V)>,
+        capacity: usize,
+        stats: CacheStats,
+        on_evict: Option<EvictionCallback<K, V>>,
+    }
+
+    // the map only points into nodes owned by the list, so they always move
+    // between threads together and the cache is Send whenever its entries are
+    unsafe impl<K: Send, V: Send> Send for LruCache<K, V> {}
+
+    impl<K: Eq + Hash, V> LruCache<K, V> {
+        pub fn new(capacity: usize) -> Self {
+            assert!(capacity > 0, "capacity must be at least 1");
+            Self {
+                map: HashMap::with_capacity(capacity),
+                list: LinkedList::new(),
+                capacity,
+                stats: CacheStats::default(),
+                on_evict: None,
+            }
+        }
+
+        // creates a cache that hands every evicted entry to on_evict
+        pub fn with_eviction_callback<F: FnMut(K, V) + Send + 'static>(
+            capacity: usize,
+            on_evict: F,
+        ) -> Self {
+            let mut cache = Self::new(capacity);
+            cache.on_evict = Some(Box::new(on_evict));
+            cache
+        }
+
+        // inserts or updates key and marks it as most recently used
+        // returns the previous value of key, if any; evicted entries go to the callback
+        pub fn put(&mut self, key: K, value: V) -> Option<V> {
+            if let Some(&handle) = self.map.get(KeyWrapper::from_ref(&key)) {
+                unsafe {
+                    self.list.move_to_front(handle);
+                    return Some(mem::replace(&mut *value_ptr(handle), value));
+                }
+            }
+            if self.len() == self.capacity {
+                self.evict();
+            }
+            let handle = self.list.push_front_handle((key, value));
+            self.map.insert(KeyRef(unsafe { key_ptr(handle) }), handle);
+            None
+        }
+
+        // returns the value of key and marks it as most recently used
+        pub fn get<Q>(&mut self, key: &Q) -> Option<&V>
+        where
+            K: Borrow<Q>,
+            Q: Hash + Eq + ?Sized,
+        {
+            let handle = self.lookup(key)?;
+            Some(unsafe { &*value_ptr(handle) })
+        }
+
+        pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+        where
+            K: Borrow<Q>,
+            Q: Hash + Eq + ?Sized,
+        {
+            let handle = self.lookup(key)?;
+            Some(unsafe { &mut *value_ptr(handle) })
+        }
+
+        // returns the value of key without marking it as used or counting the lookup
+        pub fn peek<Q>(&self, key: &Q) -> Option<&V>
+        where
+            K: Borrow<Q>,
+            Q: Hash + Eq + ?Sized,
+        {
+            let &handle = self.map.get(KeyWrapper::from_ref(key))?;
+            Some(unsafe { &*value_ptr(handle) })
+        }
+
+        // returns the entry that would be evicted next
+        pub fn peek_lru(&self) -> Option<(&K, &V)> {
+            self.list.back().map(|(key, value)| (key, value))
+        }
+
+        pub fn contains<Q>(&self, key: &Q) -> bool
+        where
+            K: Borrow<Q>,
+            Q: Hash + Eq + ?Sized,
+        {
+            self.map.contains_key(KeyWrapper::from_ref(key))
+        }
+
+        // removes key and returns its value, without calling the eviction callback
+        pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+        where
+            K: Borrow<Q>,
+            Q: Hash + Eq + ?Sized,
+        {
+            let handle = self.map.remove(KeyWrapper::from_ref(key))?;
+            Some(unsafe { self.list.remove_handle(handle) }.1)
+        }
+
+        // removes and returns the least recently used entry
+        pub fn pop_lru(&mut self) -> Option<(K, V)> {
+            let handle = self.list.back_handle()?;
+            unsafe {
+                self.map
+                    .remove(KeyWrapper::from_ref(key_ptr(handle).as_ref()));
+                Some(self.list.remove_handle(handle))
+            }
+        }
+
+        // iterates from the most to the least recently used entry
+        pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+            self.list.iter().map(|(key, value)| (key, value))
+        }
+
+        pub fn len(&self) -> usize {
+            self.list.len()
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.list.is_empty()
+        }
+
+        pub fn capacity(&self) -> usize {
+            self.capacity
+        }
+
+        pub fn stats(&self) -> CacheStats {
+            self.stats
+        }
+
+        // finds key for get and get_mut, counting the hit or miss
+        fn lookup<Q>(&mut self, key: &Q) -> Option<Handle<K, V>>
+        where
+            K: Borrow<Q>,
+            Q: Hash + Eq + ?Sized,
+        {
+            match self.map.get(KeyWrapper::from_ref(key)).copied() {
+                Some(handle) => {
+                    self.stats.hits += 1;
+                    unsafe { self.list.move_to_front(handle) };
+                    Some(handle)
+                }
+                None => {
+                    self.stats.misses += 1;
+                    None
+                }
+            }
+        }
+
+        fn evict(&mut self) {
+            if let Some((key, value)) = self.pop_lru() {
+                self.stats.evictions += 1;
+                if let Some(on_evict) = &mut self.on_evict {
+                    on_evict(key, value);
+                }
+            }
+        }
+    }
+
+    // A least frequently used cache: once full, putting a new key evicts the entry
+    // with the fewest gets and puts, and among those the least recently used one.
+    // Entries are kept in one bucket per use count, and the buckets in a list of their
+    // own ordered by use count, so a use moves an entry's node to the bucket after its
+    // own, creating that one if needed, and every operation is O(1).
+    pub struct LfuCache<K, V> {
+        // key -> its node and the bucket holding it
+        map: HashMap<KeyRef<K>, Slot<K, V>>,
+        // in increasing order of use count, holding only counts some entry has,
+        // so the next victim is at the back of the front bucket
+        buckets: LinkedList<Bucket<K, V>>,
+        capacity: usize,
+        stats: CacheStats,
+        on_evict: Option<EvictionCallback<K, V>>,
+    }
+
+    // the entries used freq times, most recently used at the front
+    struct Bucket<K, V> {
+        freq: usize,
+        entries: LinkedList<(K, V)>,
+    }
+
+    impl<K, V> Bucket<K, V> {
+        fn new(freq: usize) -> Self {
+            Bucket {
+                freq,
+                entries: LinkedList::new(),
+            }
+        }
+    }
+
+    type Slot<K, V> = (Handle<K, V>, NodeHandle<Bucket<K, V>>);
+
+    // the bucket in the node of handle, which must be alive
+    // the reference only covers the bucket, so the list can still relink its node
+    unsafe fn bucket_mut<'a, K, V>(handle: NodeHandle<Bucket<K, V>>) -> &'a mut Bucket<K, V> {
+        unsafe { &mut *handle.element().as_ptr() }
+    }
+
+    // as for LruCache, the map only points into nodes owned by the buckets
+    unsafe impl<K: Send, V: Send> Send for LfuCache<K, V> {}
+
+    impl<K: Eq + Hash, V> LfuCache<K, V> {
+        pub fn new(capacity: usize) -> Self {
+            assert!(capacity > 0, "capacity must be at least 1");
+            Self {
+                map: HashMap::with_capacity(capacity),
+                buckets: LinkedList::new(),
+                capacity,
+                stats: CacheStats::default(),
+                on_evict: None,
+            }
+        }
+
+        // creates a cache that hands every evicted entry to on_evict
+        pub fn with_eviction_callback<F: FnMut(K, V) + Send + 'static>(
+            capacity: usize,
+            on_evict: F,
+        ) -> Self {
+            let mut cache = Self::new(capacity);
+            cache.on_evict = Some(Box::new(on_evict));
+            cache
+        }
+
+        // inserts or updates key, counting as one use of it
+        // returns the previous value of key, if any; evicted entries go to the callback
+        pub fn put(&mut self, key: K, value: V) -> Option<V> {
+            if let Some(handle) = self.touch(&key) {
+                return Some(mem::replace(unsafe { &mut *value_ptr(handle) }, value));
+            }
+            if self.len() == self.capacity {
+                self.evict();
+            }
+            let bucket = match self.buckets.front_handle() {
+                Some(front) if unsafe { bucket_mut(front) }.freq == 1 => front,
+                _ => self.buckets.push_front_handle(Bucket::new(1)),
+            };
+            let handle = unsafe { bucket_mut(bucket) }
+                .entries
+                .push_front_handle((key, value));
+            self.map
+                .insert(KeyRef(unsafe { key_ptr(handle) }), (handle, bucket));
+            None
+        }
+
+        // returns the value of key, counting as one use of it
+        pub fn get<Q>(&mut self, key: &Q) -> Option<&V>
+        where
+            K: Borrow<Q>,
+            Q: Hash + Eq + ?Sized,
+        {
+            let handle = self.lookup(key)?;
+            Some(unsafe { &*value_ptr(handle) })
+        }
+
+        pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+        where
+            K: Borrow<Q>,
+            Q: Hash + Eq + ?Sized,
+        {
+            let handle = self.lookup(key)?;
+            Some(unsafe { &mut *value_ptr(handle) })
+        }
+
+        // returns the value of key without counting a use or the lookup
+        pub fn peek<Q>(&self, key: &Q) -> Option<&V>
+        where
+            K: Borrow<Q>,
+            Q: Hash + Eq + ?Sized,
+        {
+            let &(handle, _) = self.map.get(KeyWrapper::from_ref(key))?;
+            Some(unsafe { &*value_ptr(handle) })
+        }
+
+        // returns how many times key has been used
+        pub fn frequency<Q>(&self, key: &Q) -> Option<usize>
+        where
+            K: Borrow<Q>,
+            Q: Hash + Eq + ?Sized,
+        {
+            let &(_, bucket) = self.map.get(KeyWrapper::from_ref(key))?;
+            Some(unsafe { bucket_mut(bucket) }.freq)
+        }
+
+        pub fn contains<Q>(&self, key: &Q) -> bool
+        where
+            K: Borrow<Q>,
+            Q: Hash + Eq + ?Sized,
+        {
+            self.map.contains_key(KeyWrapper::from_ref(key))
+        }
+
+        // removes key and returns its value, without calling the eviction callback
+        pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+        where
+            K: Borrow<Q>,
+            Q: Hash + Eq + ?Sized,
+        {
+            let slot = self.map.remove(KeyWrapper::from_ref(key))?;
+            Some(unsafe { self.take(slot) }.1)
+        }
+
+        pub fn len(&self) -> usize {
+            self.map.len()
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.len() == 0
+        }
+
+        pub fn capacity(&self) -> usize {
+            self.capacity
+        }
+
+        pub fn stats(&self) -> CacheStats {
+            self.stats
+        }
+
+        // finds key for get and get_mut, counting the hit or miss
+        fn lookup<Q>(&mut self, key: &Q) -> Option<Handle<K, V>>
+        where
+            K: Borrow<Q>,
+            Q: Hash + Eq + ?Sized,
+        {
+            let handle = self.touch(key);
+            match handle {
+                Some(_) => self.stats.hits += 1,
+                None => self.stats.misses += 1,
+            }
+            handle
+        }
+
+        // counts a use of key, moving its node to the front of the bucket after its own
+        fn touch<Q>(&mut self, key: &Q) -> Option<Handle<K, V>>
+        where
+            K: Borrow<Q>,
+            Q: Hash + Eq + ?Sized,
+        {
+            let (handle, bucket) = self.map.get_mut(KeyWrapper::from_ref(key))?;
+            unsafe {
+                let freq = bucket_mut(*bucket).freq;
+                let next = match bucket.next() {
+                    Some(next) if bucket_mut(next).freq == freq + 1 => next,
+                    _ => self
+                        .buckets
+                        .insert_after_handle(*bucket, Bucket::new(freq + 1)),
+                };
+                let entries = &mut bucket_mut(*bucket).entries;
+                entries.move_to_front_of(*handle, &mut bucket_mut(next).entries);
+                if entries.is_empty() {
+                    self.buckets.remove_handle(*bucket);
+                }
+                *bucket = next;
+            }
+            Some(*handle)
+        }
+
+        fn evict(&mut self) {
+            let Some(bucket) = self.buckets.front_handle() else {
+                return;
+            };
+            let (key, value) = unsafe {
+                let handle = bucket_mut(bucket).entries.back_handle().unwrap();
+                self.map
+                    .remove(KeyWrapper::from_ref(key_ptr(handle).as_ref()));
+                self.take((handle, bucket))
+            };
+            self.stats.evictions += 1;
+            if let Some(on_evict) = &mut self.on_evict {
+                on_evict(key, value);
+            }
+        }
+
+        // removes the entry of slot, and its bucket if that ends up empty
+        // the slot must already be out of the map
+        unsafe fn take(&mut self, (handle, bucket): Slot<K, V>) -> (K, V) {
+            unsafe {
+                let entries = &mut bucket_mut(bucket).entries;
+                let entry = entries.remove_handle(handle);
+                if entries.is_empty() {
+                    self.buckets.remove_handle(bucket);
+                }
+                entry
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use std::sync::{Arc, Mutex};
+
+        // Miri runs the model tests for fewer steps, they are still long enough to fill
+        // the caches and evict many times over
+        const MODEL_STEPS: usize = if cfg!(miri) { 300 } else { 5_000 };
+
+        // a tiny deterministic generator for the model tests
+        fn next_random(state: &mut u64) -> u64 {
+            *state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            *state >> 33
+        }
+
+        #[test]
+        fn test_lru_evicts_least_recently_used() {
+            let mut cache = LruCache::new(2);
+            assert_eq!(cache.put("a", 1), None);
+            assert_eq!(cache.put("b", 2), None);
+            assert_eq!(cache.get("a"), Some(&1));
+
+            // b is now the least recently used
+            assert_eq!(cache.peek_lru(), Some((&"b", &2)));
+            cache.put("c", 3);
+            assert!(!cache.contains("b"));
+            assert_eq!(cache.len(), 2);
+            assert_eq!(cache.iter().collect::<Vec<_>>(), [(&"c", &3), (&"a", &1)]);
+
+            // putting an existing key updates it and counts as a use
+            assert_eq!(cache.put("a", 10), Some(1));
+            cache.put("d", 4);
+            assert_eq!(cache.peek("a"), Some(&10));
+            assert_eq!(cache.peek("c"), None);
+        }
+
+        #[test]
+        fn test_lru_peek_does_not_promote() {
+            let mut cache = LruCache::new(2);
+            cache.put(1, "one");
+            cache.put(2, "two");
+            assert_eq!(cache.peek(&1), Some(&"one"));
+            cache.put(3, "three");
+            assert_eq!(cache.peek(&1), None);
+            // peek counts neither as a hit nor as a miss
+            let stats = cache.stats();
+            assert_eq!((stats.hits, stats.misses, stats.evictions), (0, 0, 1));
+        }
+
+        #[test]
+        fn test_lru_eviction_callback_and_stats() {
+            let evicted = Arc::new(Mutex::new(Vec::new()));
+            let mut cache = {
+                let evicted = evicted.clone();
+                LruCache::with_eviction_callback(3, move |k, v| {
+                    evicted.lock().unwrap().push((k, v))
+                })
+            };
+            for i in 0..6 {
+                cache.put(i, i * 10);
+            }
+            assert_eq!(cache.get(&4), Some(&40));
+            assert_eq!(cache.get(&0), None);
+            *cache.get_mut(&5).unwrap() += 1;
+
+            // explicit removal is not an eviction
+            assert_eq!(cache.remove(&3), Some(30));
+            assert_eq!(cache.pop_lru(), Some((4, 40)));
+            assert_eq!(*evicted.lock().unwrap(), [(0, 0), (1, 10), (2, 20)]);
+
+            let stats = cache.stats();
+            assert_eq!((stats.hits, stats.misses, stats.evictions), (2, 1, 3));
+            assert!((stats.hit_rate() - 2.0 / 3.0).abs() < 1e-9);
+            assert_eq!(cache.peek(&5), Some(&51));
+        }
+
+        #[test]
+        fn test_lru_matches_model() {
+            // the model keeps keys from least to most recently used
+            let mut model: Vec<(u64, u64)> = Vec::new();
+            let mut cache = LruCache::new(8);
+            let mut state = 7;
+            for step in 0..MODEL_STEPS as u64 {
+                let key = next_random(&mut state) % 16;
+                match next_random(&mut state) % 3 {
+                    0 => {
+                        let expected = model.iter().position(|&(k, _)| k == key).map(|p| {
+                            let entry = model.remove(p);
+                            model.push(entry);
+                            entry.1
+                        });
+                        assert_eq!(cache.get(&key).copied(), expected);
+                    }
+                    1 => {
+                        let expected = model
+                            .iter()
+                            .position(|&(k, _)| k == key)
+                            .map(|p| model.remove(p).1);
+                        assert_eq!(cache.remove(&key), expected);
+                    }
+                    _ => {
+                        if let Some(p) = model.iter().position(|&(k, _)| k == key) {
+                            model.remove(p);
+                        } else if model.len() == 8 {
+                            model.remove(0);
+                        }
+                        model.push((key, step));
+                        cache.put(key, step);
+                    }
+                }
+                let order: Vec<u64> = cache.iter().map(|(&k, _)| k).collect();
+                let expected: Vec<u64> = model.iter().rev().map(|&(k, _)| k).collect();
+                assert_eq!(order, expected);
+            }
+        }
+
+        #[test]
+        fn test_lfu_evicts_least_frequently_used() {
+            let mut cache = LfuCache::new(3);
+            cache.put("a", 1);
+            cache.put("b", 2);
+            cache.put("c", 3);
+            cache.get("a");
+            cache.get("a");
+            cache.get("b");
+            assert_eq!(cache.frequency("a"), Some(3));
+            assert_eq!(cache.frequency("c"), Some(1));
+
+            // c has been used least
+            cache.put("d", 4);
+            assert!(!cache.contains("c"));
+            // d has been used once, fewer than b, so it goes next
+            cache.put("e", 5);
+            assert!(!cache.contains("d"));
+            assert_eq!(cache.len(), 3);
+
+            // among equally used entries the least recently used one goes
+            cache.get("e");
+            cache.put("f", 6);
+            assert!(!cache.contains("b"));
+            assert!(cache.contains("e"));
+            assert_eq!(cache.peek("a"), Some(&1));
+            assert_eq!(cache.frequency("a"), Some(3));
+        }
+
+        #[test]
+        fn test_lfu_remove_keeps_eviction_order() {
+            let evicted = Arc::new(Mutex::new(Vec::new()));
+            let mut cache = {
+                let evicted = evicted.clone();
+                LfuCache::with_eviction_callback(3, move |k, _| evicted.lock().unwrap().push(k))
+            };
+            cache.put(1, ());
+            cache.put(2, ());
+            cache.put(3, ());
+            cache.get(&2);
+            cache.get(&3);
+            cache.get(&3);
+
+            // removing the only entry with one use leaves 2 as the least used
+            assert_eq!(cache.remove(&1), Some(()));
+            assert_eq!(cache.put(4, ()), None);
+            cache.get(&4);
+            cache.get(&4);
+            cache.put(5, ());
+            assert_eq!(*evicted.lock().unwrap(), [2]);
+            assert_eq!(cache.get(&2), None);
+            assert_eq!(cache.stats().evictions, 1);
+            assert_eq!(cache.stats().misses, 1);
+        }
+
+        #[test]
+        fn test_lfu_keeps_only_used_buckets() {
+            let mut cache = LfuCache::new(3);
+            cache.put("a", 1);
+            cache.put("b", 2);
+            for _ in 0..100 {
+                cache.get("a");
+            }
+            // a's bucket moved along with it instead of leaving empty ones behind
+            assert_eq!(cache.buckets.len(), 2);
+            assert_eq!(cache.frequency("a"), Some(101));
+
+            cache.get("b");
+            cache.put("c", 3);
+            let freqs: LinkedList<usize> = cache.buckets.iter().map(|b| b.freq).collect();
+            assert_eq!(freqs, LinkedList::from_iter([1, 2, 101]));
+
+            cache.remove("a");
+            cache.remove("c");
+            assert_eq!(cache.buckets.len(), 1);
+            assert_eq!(cache.buckets.front().map(|b| b.freq), Some(2));
+        }
+
+        #[test]
+        fn test_caches_are_send() {
+            // fails to compile if either cache stops being Send
+            fn assert_send<T: Send>() {}
+            assert_send::<LruCache<String, Vec<u8>>>();
+            assert_send::<LfuCache<String, Vec<u8>>>();
+
+            let cache = Arc::new(Mutex::new(LruCache::with_eviction_callback(4, |_, _| {})));
+            let handles: Vec<_> = (0..4)
+                .map(|t| {
+                    let cache = cache.clone();
+                    std::thread::spawn(move || {
+                        for i in 0..100 {
+                            cache.lock().unwrap().put(t * 100 + i, i);
+                        }
+                    })
+                })
+                .collect();
+            for handle in handles {
+                handle.join().unwrap();
+            }
+            let cache = cache.lock().unwrap();
+            assert_eq!(cache.len(), 4);
+            assert_eq!(cache.stats().evictions, 396);
+        }
+
+        #[test]
+        fn test_lfu_matches_model() {
+            // the model keeps (key, value, uses, last use) and evicts the fewest uses, then the oldest
+            let mut model: Vec<(u64, u64, usize, usize)> = Vec::new();
+            let mut cache = LfuCache::new(6);
+            let mut state = 11;
+            for step in 0..MODEL_STEPS {
+                let key = next_random(&mut state) % 12;
+                let found = model.iter().position(|e| e.0 == key);
+                match next_random(&mut state) % 3 {
+                    0 => {
+                        let expected = found.map(|p| {
+                            model[p].2 += 1;
+                            model[p].3 = step;
+                            model[p].1
+                        });
+                        assert_eq!(cache.get(&key).copied(), expected);
+                    }
+                    1 => {
+                        let expected = found.map(|p| model.remove(p).1);
+                        assert_eq!(cache.remove(&key), expected);
+                    }
+                    _ => {
+                        match found {
+                            Some(p) => model[p] = (key, step as u64, model[p].2 + 1, step),
+                            None => {
+                                if model.len() == 6 {
+                                    let victim = (0..model.len())
+                                        .min_by_key(|&p| (model[p].2, model[p].3))
+                                        .unwrap();
+                                    model.remove(victim);
+                                }
+                                model.push((key, step as u64, 1, step));
+                            }
+                        }
+                        cache.put(key, step as u64);
+                    }
+                }
+                for &(k, v, uses, _) in &model {
+                    assert_eq!(cache.peek(&k), Some(&v));
+                    assert_eq!(cache.frequency(&k), Some(uses));
+                }
+                assert_eq!(cache.len(), model.len());
+            }
+        }
+    }
+}
@@ -0,0 +1,59 @@
+pub mod failing_alloc {
+    use std::{
+        alloc::{GlobalAlloc, Layout, System},
+        cell::Cell,
+    };
+
+    // the global allocator of the test binary: it forwards everything to the system
+    // allocator, except that it returns null on threads that asked for failures
+    struct FailingAlloc;
+
+    thread_local! {
+        static FAILING: Cell<bool> = const { Cell::new(false) };
+    }
+
+    fn failing() -> bool {
+        FAILING.try_with(Cell::get).unwrap_or(false)
+    }
+
+    unsafe impl GlobalAlloc for FailingAlloc {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            if failing() {
+                std::ptr::null_mut()
+            } else {
+                unsafe { System.alloc(layout) }
+            }
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            unsafe { System.dealloc(ptr, layout) }
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            if failing() {
+                std::ptr::null_mut()
+            } else {
+                unsafe { System.realloc(ptr, layout, new_size) }
+            }
+        }
+    }
+
+    #[global_allocator]
+    static GLOBAL: FailingAlloc = FailingAlloc;
+
+    // turns failures back off even if f panics, so the panic itself can allocate
+    struct Reset;
+
+    impl Drop for Reset {
+        fn drop(&mut self) {
+            FAILING.with(|failing| failing.set(false));
+        }
+    }
+
+    // runs f with every allocation and reallocation on the current thread failing
+    pub fn with_failing_allocations<R>(f: impl FnOnce() -> R) -> R {
+        FAILING.with(|failing| failing.set(true));
+        let _reset = Reset;
+        f()
+    }
+}
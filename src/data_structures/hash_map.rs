@@ -1,50 +1,99 @@
 pub mod hash_map {
     use std::{
-        collections::{hash_map::DefaultHasher, LinkedList},
-        hash::Hasher,
+        borrow::Borrow,
+        collections::{hash_map::RandomState, LinkedList},
+        hash::{BuildHasher, Hash},
+        mem,
     };
 
-    pub struct HashMap<K, V>
-    where
-        K: Clone,
-        V: Clone,
-    {
+    // the map grows once it holds more than 3 entries for every 4 buckets
+    // and shrinks again when fewer than 1 in 8 buckets would be used
+    const MAX_LOAD_NUMERATOR: usize = 3;
+    const MAX_LOAD_DENOMINATOR: usize = 4;
+    const MIN_LOAD_DENOMINATOR: usize = 8;
+    const MIN_BUCKETS: usize = 8;
+
+    // S builds the hasher used for the keys; the default is SipHash with a random seed,
+    // see the hasher module for faster or reproducible alternatives
+    pub struct HashMap<K, V, S = RandomState> {
         buckets: Vec<LinkedList<(K, V)>>,
         size: usize,
+        len: usize,
+        // automatic shrinking never goes below the bucket count the caller asked for
+        min_size: usize,
+        hash_builder: S,
     }
 
-    impl<K: Eq + std::hash::Hash + Clone, V: Clone> HashMap<K, V> {
+    impl<K: Eq + Hash, V> HashMap<K, V> {
+        // creates a map with exactly size buckets; a size of 0 allocates on the first insert
         pub fn new(size: usize) -> Self {
+            Self::with_size_and_hasher(size, RandomState::new())
+        }
+
+        // creates a map that can hold at least capacity entries without growing
+        pub fn with_capacity(capacity: usize) -> Self {
+            Self::with_capacity_and_hasher(capacity, RandomState::new())
+        }
+    }
+
+    impl<K: Eq + Hash, V, S: BuildHasher> HashMap<K, V, S> {
+        // creates an empty map that hashes its keys with hash_builder
+        pub fn with_hasher(hash_builder: S) -> Self {
+            Self::with_size_and_hasher(0, hash_builder)
+        }
+
+        pub fn with_capacity_and_hasher(capacity: usize, hash_builder: S) -> Self {
+            Self::with_size_and_hasher(buckets_for(capacity), hash_builder)
+        }
+
+        fn with_size_and_hasher(size: usize, hash_builder: S) -> Self {
             Self {
-                buckets: vec![LinkedList::new(); size],
+                buckets: empty_buckets(size),
                 size,
+                len: 0,
+                min_size: size,
+                hash_builder,
             }
         }
 
-        pub fn insert(&mut self, key: K, value: V) {
-            let mut hasher = DefaultHasher::new();
-            key.hash(&mut hasher);
-            let bucket = (hasher.finish() % self.size as u64) as usize;
-            let bucket_list = &mut self.buckets[bucket];
+        pub fn hasher(&self) -> &S {
+            &self.hash_builder
+        }
 
-            for &mut (ref e_key, ref mut e_value) in bucket_list.iter_mut() {
-                if e_key == &key {
-                    *e_value = value.clone();
-                    return;
-                }
+        // inserts a key-value pair and returns the value previously stored under key, if any
+        pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+            if let Some(e_value) = self.get_mut(&key) {
+                return Some(mem::replace(e_value, value));
             }
+            self.insert_new(key, value);
+            None
+        }
 
+        // appends a pair whose key is known not to be in the map, growing first if needed
+        // returns a reference to the freshly stored value
+        fn insert_new(&mut self, key: K, value: V) -> &mut V {
+            if self.len + 1 > self.capacity() {
+                self.resize((self.size * 2).max(MIN_BUCKETS));
+            }
+            let bucket = self.bucket_index(&key);
+            self.len += 1;
+            let bucket_list = &mut self.buckets[bucket];
             bucket_list.push_back((key, value));
+            &mut bucket_list.back_mut().unwrap().1
         }
 
-        pub fn search(&self, key: &K) -> Option<&V> {
-            let mut hasher = DefaultHasher::new();
-            key.hash(&mut hasher);
-            let bucket = (hasher.finish() % self.size as u64) as usize;
-            let bucket_list = &self.buckets[bucket];
+        pub fn search<Q>(&self, key: &Q) -> Option<&V>
+        where
+            K: Borrow<Q>,
+            Q: Hash + Eq + ?Sized,
+        {
+            if self.size == 0 {
+                return None;
+            }
+            let bucket_list = &self.buckets[self.bucket_index(key)];
 
-            for &(ref e_key, ref e_value) in bucket_list.iter() {
-                if e_key == key {
+            for (e_key, e_value) in bucket_list.iter() {
+                if e_key.borrow() == key {
                     return Some(e_value);
                 }
             }
@@ -52,34 +101,84 @@ pub mod hash_map {
             None
         }
 
-        pub fn delete(&mut self, key: &K) {
-            let mut hasher = DefaultHasher::new();
-            key.hash(&mut hasher);
-            let bucket = (hasher.finish() % self.size as u64) as usize;
-            let bucket_list = &mut self.buckets[bucket];
+        pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+        where
+            K: Borrow<Q>,
+            Q: Hash + Eq + ?Sized,
+        {
+            if self.size == 0 {
+                return None;
+            }
+            let bucket = self.bucket_index(key);
+            self.buckets[bucket]
+                .iter_mut()
+                .find(|(e_key, _)| (*e_key).borrow() == key)
+                .map(|(_, e_value)| e_value)
+        }
 
-            let mut i = 0;
-            for (ref e_key, _) in bucket_list.iter() {
-                if e_key == key {
-                    break;
-                }
-                i += 1;
+        // returns the entry for key, to be inspected or updated in place
+        pub fn entry(&mut self, key: K) -> Entry<'_, K, V, S> {
+            if !self.contains_key(&key) {
+                return Entry::Vacant(VacantEntry { map: self, key });
             }
+            let bucket = self.bucket_index(&key);
+            let (key, value) = self.buckets[bucket]
+                .iter_mut()
+                .find(|(e_key, _)| *e_key == key)
+                .unwrap();
+            Entry::Occupied(OccupiedEntry { key, value })
+        }
 
-            if i < bucket_list.len() {
-                let mut rest = bucket_list.split_off(i);
-                if i < rest.len() {
-                    rest.pop_front();
-                }
-                bucket_list.append(&mut rest);
+        // removes the key and returns its value, or None if the key was not in the map
+        pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+        where
+            K: Borrow<Q>,
+            Q: Hash + Eq + ?Sized,
+        {
+            if self.size == 0 {
+                return None;
             }
+            let bucket = self.bucket_index(key);
+            let bucket_list = &mut self.buckets[bucket];
+
+            let i = bucket_list
+                .iter()
+                .position(|(e_key, _)| e_key.borrow() == key)?;
+            let mut rest = bucket_list.split_off(i);
+            let (_, value) = rest.pop_front().unwrap();
+            bucket_list.append(&mut rest);
+            self.len -= 1;
+
+            // like growth, shrinking stops at MIN_BUCKETS so that a nearly empty map
+            // does not rehash on every insert and remove
+            let floor = self.min_size.max(MIN_BUCKETS);
+            if self.size > floor && self.len * MIN_LOAD_DENOMINATOR < self.size {
+                self.resize((self.size / 2).max(floor));
+            }
+            Some(value)
         }
 
-        pub fn contains_key(&self, key: &K) -> bool {
+        pub fn delete<Q>(&mut self, key: &Q)
+        where
+            K: Borrow<Q>,
+            Q: Hash + Eq + ?Sized,
+        {
+            self.remove(key);
+        }
+
+        pub fn contains_key<Q>(&self, key: &Q) -> bool
+        where
+            K: Borrow<Q>,
+            Q: Hash + Eq + ?Sized,
+        {
             self.search(key).is_some()
         }
 
-        pub fn get(&self, key: &K) -> Option<&V> {
+        pub fn get<Q>(&self, key: &Q) -> Option<&V>
+        where
+            K: Borrow<Q>,
+            Q: Hash + Eq + ?Sized,
+        {
             self.search(key)
         }
 
@@ -87,10 +186,43 @@ pub mod hash_map {
             for bucket in &mut self.buckets {
                 bucket.clear();
             }
+            self.len = 0;
         }
 
         pub fn len(&self) -> usize {
-            self.buckets.iter().map(|bucket| bucket.len()).sum()
+            self.len
+        }
+
+        // returns the number of entries the map can hold before it has to grow
+        pub fn capacity(&self) -> usize {
+            self.size * MAX_LOAD_NUMERATOR / MAX_LOAD_DENOMINATOR
+        }
+
+        // returns the average number of entries per bucket
+        pub fn load_factor(&self) -> f64 {
+            if self.size == 0 {
+                0.0
+            } else {
+                self.len as f64 / self.size as f64
+            }
+        }
+
+        // makes room for at least additional more entries without growing
+        pub fn reserve(&mut self, additional: usize) {
+            let needed = self.len + additional;
+            if needed > self.capacity() {
+                self.resize(buckets_for(needed).max(self.size * 2));
+            }
+            self.min_size = self.min_size.max(self.size);
+        }
+
+        // shrinks the bucket array as much as the current entries allow
+        pub fn shrink_to_fit(&mut self) {
+            let new_size = buckets_for(self.len);
+            self.min_size = new_size;
+            if new_size < self.size {
+                self.resize(new_size);
+            }
         }
 
         pub fn keys(&self) -> impl Iterator<Item = &K> {
@@ -111,20 +243,21 @@ pub mod hash_map {
                 .flat_map(|bucket| bucket.iter().map(|(key, value)| (key, value)))
         }
 
+        // rehashes every entry into new_size buckets, moving them rather than cloning
         pub fn resize(&mut self, new_size: usize) {
-            let mut new_buckets = vec![LinkedList::new(); new_size];
-
-            for bucket in &self.buckets {
-                for &(ref key, ref value) in bucket.iter() {
-                    let mut hasher = DefaultHasher::new();
-                    key.hash(&mut hasher);
-                    let new_bucket = (hasher.finish() % new_size as u64) as usize;
-                    new_buckets[new_bucket].push_back((key.clone(), value.clone()));
+            assert!(
+                new_size > 0 || self.len == 0,
+                "cannot resize a non-empty map to zero buckets"
+            );
+            let old_buckets = mem::replace(&mut self.buckets, empty_buckets(new_size));
+            self.size = new_size;
+
+            for bucket in old_buckets {
+                for (key, value) in bucket {
+                    let new_bucket = self.bucket_index(&key);
+                    self.buckets[new_bucket].push_back((key, value));
                 }
             }
-
-            self.buckets = new_buckets;
-            self.size = new_size;
         }
 
         pub fn is_empty(&self) -> bool {
@@ -134,11 +267,136 @@ pub mod hash_map {
         pub fn size(&self) -> usize {
             self.len()
         }
+
+        fn bucket_index<Q>(&self, key: &Q) -> usize
+        where
+            K: Borrow<Q>,
+            Q: Hash + Eq + ?Sized,
+        {
+            (self.hash_builder.hash_one(key) % self.size as u64) as usize
+        }
+    }
+
+    impl<K: Eq + Hash, V, S: BuildHasher + Default> Default for HashMap<K, V, S> {
+        fn default() -> Self {
+            Self::with_hasher(S::default())
+        }
+    }
+
+    // A view into a single key of the map, which is either occupied or vacant
+    pub enum Entry<'a, K, V, S = RandomState> {
+        Occupied(OccupiedEntry<'a, K, V>),
+        Vacant(VacantEntry<'a, K, V, S>),
+    }
+
+    pub struct OccupiedEntry<'a, K, V> {
+        key: &'a K,
+        value: &'a mut V,
+    }
+
+    pub struct VacantEntry<'a, K, V, S = RandomState> {
+        map: &'a mut HashMap<K, V, S>,
+        key: K,
+    }
+
+    impl<'a, K: Eq + Hash, V, S: BuildHasher> Entry<'a, K, V, S> {
+        pub fn key(&self) -> &K {
+            match self {
+                Entry::Occupied(entry) => entry.key(),
+                Entry::Vacant(entry) => entry.key(),
+            }
+        }
+
+        // returns the value for the key, inserting default first if the entry is vacant
+        pub fn or_insert(self, default: V) -> &'a mut V {
+            match self {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => entry.insert(default),
+            }
+        }
+
+        // like or_insert, but only builds the default value when the entry is vacant
+        pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
+            match self {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => entry.insert(default()),
+            }
+        }
+
+        pub fn or_default(self) -> &'a mut V
+        where
+            V: Default,
+        {
+            self.or_insert_with(V::default)
+        }
+
+        // runs f on the value if the entry is occupied, then hands the entry back
+        pub fn and_modify<F: FnOnce(&mut V)>(self, f: F) -> Self {
+            match self {
+                Entry::Occupied(entry) => {
+                    f(entry.value);
+                    Entry::Occupied(entry)
+                }
+                Entry::Vacant(entry) => Entry::Vacant(entry),
+            }
+        }
+    }
+
+    impl<'a, K, V> OccupiedEntry<'a, K, V> {
+        pub fn key(&self) -> &K {
+            self.key
+        }
+
+        pub fn get(&self) -> &V {
+            self.value
+        }
+
+        pub fn get_mut(&mut self) -> &mut V {
+            self.value
+        }
+
+        // converts the entry into a mutable reference tied to the map's lifetime
+        pub fn into_mut(self) -> &'a mut V {
+            self.value
+        }
+
+        // replaces the value and returns the old one
+        pub fn insert(&mut self, value: V) -> V {
+            mem::replace(self.value, value)
+        }
+    }
+
+    impl<'a, K: Eq + Hash, V, S: BuildHasher> VacantEntry<'a, K, V, S> {
+        pub fn key(&self) -> &K {
+            &self.key
+        }
+
+        pub fn into_key(self) -> K {
+            self.key
+        }
+
+        // inserts the value under the entry's key and returns a mutable reference to it
+        pub fn insert(self, value: V) -> &'a mut V {
+            self.map.insert_new(self.key, value)
+        }
+    }
+
+    fn empty_buckets<K, V>(size: usize) -> Vec<LinkedList<(K, V)>> {
+        (0..size).map(|_| LinkedList::new()).collect()
+    }
+
+    // the smallest bucket count whose capacity is at least capacity
+    fn buckets_for(capacity: usize) -> usize {
+        capacity
+            .checked_mul(MAX_LOAD_DENOMINATOR)
+            .expect("capacity overflow")
+            .div_ceil(MAX_LOAD_NUMERATOR)
     }
 
     #[cfg(test)]
     mod tests {
         use super::*;
+        use crate::data_structures::hasher::hasher::{FxBuildHasher, SeededState};
 
         #[test]
         fn test_new() {
@@ -266,5 +524,272 @@ pub mod hash_map {
             assert!(!map.is_empty());
             assert_eq!(map.size(), 1);
         }
+
+        #[test]
+        fn test_new_zero_does_not_panic() {
+            let mut map: HashMap<i32, i32> = HashMap::new(0);
+            assert_eq!(map.search(&1), None);
+            map.delete(&1);
+
+            map.insert(1, 2);
+            assert_eq!(map.search(&1), Some(&2));
+            assert_eq!(map.size, MIN_BUCKETS);
+        }
+
+        #[test]
+        fn test_grows_automatically() {
+            let mut map: HashMap<i32, i32> = HashMap::new(4);
+            for i in 0..1_000 {
+                map.insert(i, i * 2);
+                assert!(map.load_factor() <= 0.75);
+            }
+
+            assert_eq!(map.len(), 1_000);
+            assert!(map.capacity() >= 1_000);
+            for i in 0..1_000 {
+                assert_eq!(map.search(&i), Some(&(i * 2)));
+            }
+        }
+
+        #[test]
+        fn test_shrinks_automatically() {
+            let mut map: HashMap<i32, i32> = HashMap::new(0);
+            for i in 0..1_000 {
+                map.insert(i, i);
+            }
+            let grown = map.size;
+            for i in 0..990 {
+                map.delete(&i);
+            }
+
+            assert!(map.size < grown);
+            assert!(map.size >= MIN_BUCKETS);
+            for i in 990..1_000 {
+                assert_eq!(map.search(&i), Some(&i));
+            }
+        }
+
+        #[test]
+        fn test_shrinking_stops_at_min_buckets() {
+            let mut map: HashMap<i32, i32> = HashMap::new(0);
+            for i in 0..100 {
+                map.insert(i, i);
+            }
+            for i in 0..100 {
+                map.delete(&i);
+                assert!(map.size >= MIN_BUCKETS);
+            }
+
+            assert!(map.is_empty());
+            assert_eq!(map.size, MIN_BUCKETS);
+        }
+
+        #[test]
+        #[should_panic(expected = "capacity overflow")]
+        fn test_with_capacity_overflow() {
+            let _: HashMap<i32, i32> = HashMap::with_capacity(usize::MAX);
+        }
+
+        #[test]
+        fn test_never_shrinks_below_requested_size() {
+            let mut map: HashMap<i32, i32> = HashMap::new(64);
+            for i in 0..10 {
+                map.insert(i, i);
+            }
+            for i in 0..10 {
+                map.delete(&i);
+            }
+
+            assert_eq!(map.size, 64);
+        }
+
+        #[test]
+        fn test_with_capacity_and_reserve() {
+            let mut map: HashMap<i32, i32> = HashMap::with_capacity(100);
+            assert!(map.capacity() >= 100);
+            let size = map.size;
+            for i in 0..100 {
+                map.insert(i, i);
+            }
+            assert_eq!(map.size, size);
+
+            map.reserve(500);
+            assert!(map.capacity() >= 600);
+            let size = map.size;
+            for i in 100..600 {
+                map.insert(i, i);
+            }
+            assert_eq!(map.size, size);
+        }
+
+        #[test]
+        fn test_shrink_to_fit() {
+            let mut map: HashMap<i32, i32> = HashMap::with_capacity(1_000);
+            for i in 0..10 {
+                map.insert(i, i);
+            }
+
+            map.shrink_to_fit();
+            assert!(map.capacity() >= 10);
+            assert!(map.size < 20);
+            for i in 0..10 {
+                assert_eq!(map.search(&i), Some(&i));
+            }
+
+            map.clear();
+            map.shrink_to_fit();
+            assert_eq!(map.size, 0);
+            map.insert(1, 1);
+            assert_eq!(map.search(&1), Some(&1));
+        }
+
+        #[test]
+        fn test_delete_missing_key_keeps_len() {
+            let mut map: HashMap<i32, i32> = HashMap::new(10);
+            map.insert(1, 2);
+            map.delete(&3);
+            map.delete(&1);
+            map.delete(&1);
+
+            assert_eq!(map.len(), 0);
+        }
+
+        #[test]
+        fn test_custom_hasher() {
+            let mut map: HashMap<u64, u64, FxBuildHasher> =
+                HashMap::with_hasher(Default::default());
+            for i in 0..1_000 {
+                map.insert(i, i + 1);
+            }
+            for i in 0..1_000 {
+                assert_eq!(map.search(&i), Some(&(i + 1)));
+            }
+
+            let map: HashMap<u64, u64, FxBuildHasher> =
+                HashMap::with_capacity_and_hasher(50, FxBuildHasher::default());
+            assert!(map.capacity() >= 50);
+
+            let map: HashMap<u64, u64, FxBuildHasher> = HashMap::default();
+            assert!(map.is_empty());
+        }
+
+        #[test]
+        fn test_seeded_hasher_gives_reproducible_order() {
+            let build = |seed| {
+                let mut map = HashMap::with_hasher(SeededState::new(seed));
+                for word in ["apple", "pear", "plum", "fig", "kiwi", "lime", "date"] {
+                    map.insert(word, word.len());
+                }
+                map.keys().copied().collect::<Vec<_>>()
+            };
+
+            assert_eq!(build(1), build(1));
+            assert_eq!(build(2), build(2));
+
+            // and the seed actually changes the hashes
+            for key in ["apple", "pear", "plum"] {
+                assert_ne!(
+                    SeededState::new(1).hash_one(key),
+                    SeededState::new(2).hash_one(key)
+                );
+            }
+        }
+
+        #[test]
+        fn test_non_clone_values() {
+            trait Shape {
+                fn area(&self) -> u32;
+            }
+            struct Square(u32);
+            impl Shape for Square {
+                fn area(&self) -> u32 {
+                    self.0 * self.0
+                }
+            }
+
+            let mut map: HashMap<String, Box<dyn Shape>> = HashMap::new(0);
+            for i in 0..100 {
+                map.insert(format!("square {}", i), Box::new(Square(i)));
+            }
+
+            assert_eq!(map.len(), 100);
+            assert_eq!(map.get(&"square 7".to_string()).unwrap().area(), 49);
+            let removed = map.remove(&"square 9".to_string()).unwrap();
+            assert_eq!(removed.area(), 81);
+        }
+
+        #[test]
+        fn test_insert_and_remove_return_values() {
+            let mut map: HashMap<i32, String> = HashMap::new(4);
+            assert_eq!(map.insert(1, "one".to_string()), None);
+            assert_eq!(map.insert(1, "uno".to_string()), Some("one".to_string()));
+            assert_eq!(map.len(), 1);
+
+            assert_eq!(map.remove(&1), Some("uno".to_string()));
+            assert_eq!(map.remove(&1), None);
+            assert!(map.is_empty());
+        }
+
+        #[test]
+        fn test_get_mut() {
+            let mut map: HashMap<i32, Vec<i32>> = HashMap::new(0);
+            assert!(map.get_mut(&1).is_none());
+            map.insert(1, vec![]);
+            map.get_mut(&1).unwrap().push(5);
+
+            assert_eq!(map.get(&1), Some(&vec![5]));
+        }
+
+        #[test]
+        fn test_entry() {
+            let mut map: HashMap<&str, usize> = HashMap::new(0);
+            for word in "one fish two fish red fish blue fish".split(' ') {
+                *map.entry(word).or_insert(0) += 1;
+            }
+            assert_eq!(map.get(&"fish"), Some(&4));
+            assert_eq!(map.get(&"red"), Some(&1));
+            assert_eq!(map.len(), 5);
+
+            map.entry("fish")
+                .and_modify(|count| *count *= 10)
+                .or_default();
+            map.entry("cat")
+                .and_modify(|count| *count *= 10)
+                .or_default();
+            assert_eq!(map.get(&"fish"), Some(&40));
+            assert_eq!(map.get(&"cat"), Some(&0));
+            assert_eq!(*map.entry("dog").or_insert_with(|| 3), 3);
+
+            match map.entry("red") {
+                Entry::Occupied(mut entry) => {
+                    assert_eq!(entry.key(), &"red");
+                    assert_eq!(entry.insert(2), 1);
+                    *entry.get_mut() += 1;
+                    assert_eq!(entry.get(), &3);
+                }
+                Entry::Vacant(_) => panic!("red should be occupied"),
+            }
+            match map.entry("green") {
+                Entry::Occupied(_) => panic!("green should be vacant"),
+                Entry::Vacant(entry) => assert_eq!(entry.into_key(), "green"),
+            }
+            assert_eq!(map.get(&"green"), None);
+        }
+
+        #[test]
+        fn test_borrowed_lookups() {
+            let mut map: HashMap<String, i32> = HashMap::new(0);
+            map.insert("one".to_string(), 1);
+            map.insert("two".to_string(), 2);
+
+            assert_eq!(map.get("one"), Some(&1));
+            assert_eq!(map.search("two"), Some(&2));
+            assert!(map.contains_key("one"));
+            assert!(!map.contains_key("three"));
+            *map.get_mut("two").unwrap() = 22;
+            assert_eq!(map.remove("two"), Some(22));
+            map.delete("one");
+            assert!(map.is_empty());
+        }
     }
 }
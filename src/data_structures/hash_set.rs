@@ -1,43 +1,205 @@
 pub mod hash_set {
-    pub struct HashSet<T> {
-        storage: Vec<T>,
+    use std::{
+        borrow::Borrow,
+        collections::hash_map::RandomState,
+        fmt,
+        hash::{BuildHasher, Hash},
+        ops::{BitAnd, BitOr, BitXor, Sub},
+    };
+
+    use crate::data_structures::hash_map::hash_map::HashMap;
+
+    // A set stored as the keys of a HashMap<T, ()>, so insert, contains
+    // and remove are O(1) on average
+    pub struct HashSet<T, S = RandomState> {
+        map: HashMap<T, (), S>,
     }
 
-    impl<T: PartialEq + Clone> HashSet<T> {
+    impl<T: Hash + Eq> HashSet<T> {
         pub fn new() -> Self {
             HashSet {
-                storage: Vec::new(),
+                map: HashMap::new(0),
             }
         }
 
-        pub fn insert(&mut self, key: T) {
-            if !self.contains(&key) {
-                self.storage.push(key);
+        // creates a set that can hold at least capacity elements without growing
+        pub fn with_capacity(capacity: usize) -> Self {
+            HashSet {
+                map: HashMap::with_capacity(capacity),
             }
         }
+    }
+
+    impl<T: Hash + Eq, S: BuildHasher> HashSet<T, S> {
+        // creates an empty set that hashes its elements with hash_builder
+        pub fn with_hasher(hash_builder: S) -> Self {
+            HashSet {
+                map: HashMap::with_hasher(hash_builder),
+            }
+        }
+
+        pub fn with_capacity_and_hasher(capacity: usize, hash_builder: S) -> Self {
+            HashSet {
+                map: HashMap::with_capacity_and_hasher(capacity, hash_builder),
+            }
+        }
+
+        // adds key to the set and returns true if it was not already present
+        pub fn insert(&mut self, key: T) -> bool {
+            self.map.insert(key, ()).is_none()
+        }
 
-        pub fn contains(&self, key: &T) -> bool {
-            self.storage.contains(key)
+        pub fn contains<Q>(&self, key: &Q) -> bool
+        where
+            T: Borrow<Q>,
+            Q: Hash + Eq + ?Sized,
+        {
+            self.map.contains_key(key)
         }
 
-        pub fn remove(&mut self, key: &T) {
-            self.storage.retain(|x| x != key);
+        // removes key from the set and returns true if it was present
+        pub fn remove<Q>(&mut self, key: &Q) -> bool
+        where
+            T: Borrow<Q>,
+            Q: Hash + Eq + ?Sized,
+        {
+            self.map.remove(key).is_some()
         }
 
         pub fn clear(&mut self) {
-            self.storage.clear();
+            self.map.clear();
         }
 
         pub fn len(&self) -> usize {
-            self.storage.len()
+            self.map.len()
         }
 
         pub fn is_empty(&self) -> bool {
-            self.storage.is_empty()
+            self.map.is_empty()
+        }
+
+        pub fn capacity(&self) -> usize {
+            self.map.capacity()
+        }
+
+        pub fn iter(&self) -> impl Iterator<Item = &T> {
+            self.map.keys()
+        }
+
+        // lazily yields every element that is in self or other, each one once
+        pub fn union<'a>(&'a self, other: &'a Self) -> impl Iterator<Item = &'a T> + 'a {
+            self.iter().chain(other.difference(self))
+        }
+
+        // lazily yields the elements present in both sets
+        // walks the smaller set and probes the larger one
+        pub fn intersection<'a>(&'a self, other: &'a Self) -> impl Iterator<Item = &'a T> + 'a {
+            let (smaller, larger) = if self.len() <= other.len() {
+                (self, other)
+            } else {
+                (other, self)
+            };
+            smaller.iter().filter(move |key| larger.contains(*key))
+        }
+
+        // lazily yields the elements of self that are not in other
+        pub fn difference<'a>(&'a self, other: &'a Self) -> impl Iterator<Item = &'a T> + 'a {
+            self.iter().filter(move |key| !other.contains(*key))
+        }
+
+        // lazily yields the elements that are in exactly one of the two sets
+        pub fn symmetric_difference<'a>(
+            &'a self,
+            other: &'a Self,
+        ) -> impl Iterator<Item = &'a T> + 'a {
+            self.difference(other).chain(other.difference(self))
+        }
+
+        // returns true if every element of self is also in other
+        pub fn is_subset(&self, other: &Self) -> bool {
+            self.len() <= other.len() && self.iter().all(|key| other.contains(key))
+        }
+
+        // returns true if every element of other is also in self
+        pub fn is_superset(&self, other: &Self) -> bool {
+            other.is_subset(self)
+        }
+
+        // returns true if the two sets have no element in common
+        pub fn is_disjoint(&self, other: &Self) -> bool {
+            self.intersection(other).next().is_none()
+        }
+    }
+
+    impl<T: Hash + Eq, S: BuildHasher + Default> Default for HashSet<T, S> {
+        fn default() -> Self {
+            Self::with_hasher(S::default())
+        }
+    }
+
+    impl<T: Hash + Eq, S: BuildHasher + Default> FromIterator<T> for HashSet<T, S> {
+        fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+            let mut set = Self::default();
+            set.extend(iter);
+            set
+        }
+    }
+
+    impl<T: Hash + Eq, S: BuildHasher> Extend<T> for HashSet<T, S> {
+        fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+            for key in iter {
+                self.insert(key);
+            }
+        }
+    }
+
+    impl<T: Hash + Eq, S: BuildHasher> PartialEq for HashSet<T, S> {
+        fn eq(&self, other: &Self) -> bool {
+            self.len() == other.len() && self.is_subset(other)
+        }
+    }
+
+    impl<T: Hash + Eq, S: BuildHasher> Eq for HashSet<T, S> {}
+
+    impl<T: Hash + Eq + fmt::Debug, S: BuildHasher> fmt::Debug for HashSet<T, S> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_set().entries(self.iter()).finish()
+        }
+    }
+
+    // &a | &b builds a new set holding the union of a and b
+    impl<T: Hash + Eq + Clone, S: BuildHasher + Default> BitOr<&HashSet<T, S>> for &HashSet<T, S> {
+        type Output = HashSet<T, S>;
+
+        fn bitor(self, rhs: &HashSet<T, S>) -> HashSet<T, S> {
+            self.union(rhs).cloned().collect()
         }
+    }
+
+    // &a & &b builds a new set holding the intersection of a and b
+    impl<T: Hash + Eq + Clone, S: BuildHasher + Default> BitAnd<&HashSet<T, S>> for &HashSet<T, S> {
+        type Output = HashSet<T, S>;
+
+        fn bitand(self, rhs: &HashSet<T, S>) -> HashSet<T, S> {
+            self.intersection(rhs).cloned().collect()
+        }
+    }
+
+    // &a - &b builds a new set holding the elements of a that are not in b
+    impl<T: Hash + Eq + Clone, S: BuildHasher + Default> Sub<&HashSet<T, S>> for &HashSet<T, S> {
+        type Output = HashSet<T, S>;
 
-        pub fn iter(&self) -> std::slice::Iter<'_, T> {
-            self.storage.iter()
+        fn sub(self, rhs: &HashSet<T, S>) -> HashSet<T, S> {
+            self.difference(rhs).cloned().collect()
+        }
+    }
+
+    // &a ^ &b builds a new set holding the elements that are in exactly one of a and b
+    impl<T: Hash + Eq + Clone, S: BuildHasher + Default> BitXor<&HashSet<T, S>> for &HashSet<T, S> {
+        type Output = HashSet<T, S>;
+
+        fn bitxor(self, rhs: &HashSet<T, S>) -> HashSet<T, S> {
+            self.symmetric_difference(rhs).cloned().collect()
         }
     }
 
@@ -45,6 +207,12 @@ pub mod hash_set {
     mod tests {
         use super::HashSet;
 
+        fn sorted<'a>(iter: impl Iterator<Item = &'a i32>) -> Vec<i32> {
+            let mut items: Vec<i32> = iter.copied().collect();
+            items.sort();
+            items
+        }
+
         #[test]
         fn test_hash_set() {
             let mut set = HashSet::new();
@@ -69,5 +237,108 @@ pub mod hash_set {
             set.clear();
             assert!(set.is_empty());
         }
+
+        #[test]
+        fn test_borrowed_lookups() {
+            let mut set = HashSet::new();
+            set.insert("element".to_string());
+            assert!(set.contains("element"));
+            assert!(!set.contains("other"));
+            set.remove("element");
+            assert!(set.is_empty());
+        }
+
+        #[test]
+        fn test_insert_and_remove_report_changes() {
+            let mut set = HashSet::new();
+            assert!(set.insert(1));
+            assert!(!set.insert(1));
+            assert_eq!(set.len(), 1);
+            assert!(set.remove(&1));
+            assert!(!set.remove(&1));
+        }
+
+        #[test]
+        fn test_many_elements() {
+            let mut set = HashSet::with_capacity(10);
+            for i in 0..10_000 {
+                set.insert(i);
+            }
+            for i in (0..10_000).step_by(2) {
+                set.remove(&i);
+            }
+
+            assert_eq!(set.len(), 5_000);
+            for i in 0..10_000 {
+                assert_eq!(set.contains(&i), i % 2 == 1);
+            }
+            let mut items: Vec<_> = set.iter().copied().collect();
+            items.sort();
+            assert_eq!(items, (1..10_000).step_by(2).collect::<Vec<_>>());
+        }
+
+        #[test]
+        fn test_custom_hasher() {
+            use crate::data_structures::hasher::hasher::FxBuildHasher;
+
+            let mut set: HashSet<u32, FxBuildHasher> = HashSet::default();
+            set.insert(5);
+            assert!(set.contains(&5));
+        }
+
+        #[test]
+        fn test_set_algebra_iterators() {
+            let a: HashSet<i32> = (1..=5).collect();
+            let b: HashSet<i32> = (4..=8).collect();
+
+            assert_eq!(sorted(a.union(&b)), vec![1, 2, 3, 4, 5, 6, 7, 8]);
+            assert_eq!(sorted(a.intersection(&b)), vec![4, 5]);
+            assert_eq!(sorted(b.intersection(&a)), vec![4, 5]);
+            assert_eq!(sorted(a.difference(&b)), vec![1, 2, 3]);
+            assert_eq!(sorted(b.difference(&a)), vec![6, 7, 8]);
+            assert_eq!(sorted(a.symmetric_difference(&b)), vec![1, 2, 3, 6, 7, 8]);
+
+            // the iterators are lazy: taking one element doesn't walk the whole set
+            assert_eq!(a.union(&b).take(1).count(), 1);
+        }
+
+        #[test]
+        fn test_subset_superset_disjoint() {
+            let small: HashSet<i32> = [2, 3].into_iter().collect();
+            let large: HashSet<i32> = (1..=5).collect();
+            let other: HashSet<i32> = (10..12).collect();
+            let empty: HashSet<i32> = HashSet::new();
+
+            assert!(small.is_subset(&large));
+            assert!(!large.is_subset(&small));
+            assert!(large.is_superset(&small));
+            assert!(!small.is_superset(&large));
+            assert!(empty.is_subset(&small));
+            assert!(small.is_disjoint(&other));
+            assert!(!small.is_disjoint(&large));
+            assert!(empty.is_disjoint(&empty));
+        }
+
+        #[test]
+        fn test_set_operators() {
+            let a: HashSet<i32> = (1..=5).collect();
+            let b: HashSet<i32> = (4..=8).collect();
+
+            assert_eq!(&a | &b, (1..=8).collect());
+            assert_eq!(&a & &b, [4, 5].into_iter().collect());
+            assert_eq!(&a - &b, [1, 2, 3].into_iter().collect());
+            assert_eq!(&a ^ &b, [1, 2, 3, 6, 7, 8].into_iter().collect());
+            assert_ne!(&a - &b, &b - &a);
+        }
+
+        #[test]
+        fn test_extend_and_debug() {
+            let mut set = HashSet::new();
+            set.extend(vec![1, 1, 2]);
+            assert_eq!(set.len(), 2);
+
+            let single: HashSet<i32> = [7].into_iter().collect();
+            assert_eq!(format!("{:?}", single), "{7}");
+        }
     }
 }
@@ -0,0 +1,125 @@
+pub mod hasher {
+    use std::{
+        collections::hash_map::DefaultHasher,
+        hash::{BuildHasher, BuildHasherDefault, Hasher},
+    };
+
+    const FX_SEED: u64 = 0x51_7c_c1_b7_27_22_0a_95;
+
+    // The hash used inside rustc: one rotate, xor and multiply per word.
+    // It is very fast on integer keys but offers no protection against
+    // adversarial inputs, so only use it for keys you trust.
+    #[derive(Default, Clone, Copy)]
+    pub struct FxHasher {
+        hash: u64,
+    }
+
+    impl FxHasher {
+        fn add_to_hash(&mut self, word: u64) {
+            self.hash = (self.hash.rotate_left(5) ^ word).wrapping_mul(FX_SEED);
+        }
+    }
+
+    impl Hasher for FxHasher {
+        fn write(&mut self, bytes: &[u8]) {
+            let mut chunks = bytes.chunks_exact(8);
+            for chunk in &mut chunks {
+                self.add_to_hash(u64::from_le_bytes(chunk.try_into().unwrap()));
+            }
+            let rest = chunks.remainder();
+            if !rest.is_empty() {
+                let mut word = [0u8; 8];
+                word[..rest.len()].copy_from_slice(rest);
+                self.add_to_hash(u64::from_le_bytes(word));
+            }
+        }
+
+        fn write_u8(&mut self, i: u8) {
+            self.add_to_hash(i as u64);
+        }
+
+        fn write_u16(&mut self, i: u16) {
+            self.add_to_hash(i as u64);
+        }
+
+        fn write_u32(&mut self, i: u32) {
+            self.add_to_hash(i as u64);
+        }
+
+        fn write_u64(&mut self, i: u64) {
+            self.add_to_hash(i);
+        }
+
+        fn write_usize(&mut self, i: usize) {
+            self.add_to_hash(i as u64);
+        }
+
+        fn finish(&self) -> u64 {
+            self.hash
+        }
+    }
+
+    // builds FxHashers, e.g. HashMap::with_hasher(FxBuildHasher::default())
+    pub type FxBuildHasher = BuildHasherDefault<FxHasher>;
+
+    // Builds SipHash hashers that all start from the same seed, so two maps built
+    // with the same seed place the same keys in the same buckets.
+    // This makes iteration order reproducible, which is handy in tests.
+    #[derive(Clone, Copy)]
+    pub struct SeededState {
+        seed: u64,
+    }
+
+    impl SeededState {
+        pub fn new(seed: u64) -> Self {
+            Self { seed }
+        }
+
+        pub fn seed(&self) -> u64 {
+            self.seed
+        }
+    }
+
+    impl BuildHasher for SeededState {
+        type Hasher = DefaultHasher;
+
+        fn build_hasher(&self) -> DefaultHasher {
+            let mut hasher = DefaultHasher::new();
+            hasher.write_u64(self.seed);
+            hasher
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_fx_hasher_is_deterministic() {
+            let build = FxBuildHasher::default();
+            assert_eq!(build.hash_one(42u64), build.hash_one(42u64));
+            assert_ne!(build.hash_one(42u64), build.hash_one(43u64));
+            assert_eq!(build.hash_one("hello"), build.hash_one("hello"));
+            assert_ne!(build.hash_one("hello"), build.hash_one("hellp"));
+        }
+
+        #[test]
+        fn test_fx_hasher_reads_partial_words() {
+            let mut a = FxHasher::default();
+            a.write(&[1, 2, 3, 4, 5, 6, 7, 8, 9]);
+            let mut b = FxHasher::default();
+            b.write(&[1, 2, 3, 4, 5, 6, 7, 8, 10]);
+            assert_ne!(a.finish(), b.finish());
+        }
+
+        #[test]
+        fn test_seeded_state() {
+            let a = SeededState::new(7);
+            let b = SeededState::new(7);
+            let c = SeededState::new(8);
+            assert_eq!(a.seed(), 7);
+            assert_eq!(a.hash_one("key"), b.hash_one("key"));
+            assert_ne!(a.hash_one("key"), c.hash_one("key"));
+        }
+    }
+}
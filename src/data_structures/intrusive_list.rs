@@ -0,0 +1,685 @@
+pub mod intrusive_list {
+    use std::{
+        cell::Cell,
+        marker::{PhantomData, PhantomPinned},
+        pin::Pin,
+        ptr::NonNull,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    // The link fields a struct embeds to become an element of an IntrusiveList.
+    // They are Cells because the list only ever holds shared references to its elements,
+    // and PhantomPinned makes the struct !Unpin, so it has to be pinned before it can be
+    // linked: once pinned it can never move, so the pointers to it stay valid.
+    pub struct Links<T> {
+        prev: Cell<Option<NonNull<T>>>,
+        next: Cell<Option<NonNull<T>>>,
+        // the id of the list this element is linked into, or 0 when it is not linked
+        list: Cell<usize>,
+        _pinned: PhantomPinned,
+    }
+
+    impl<T> Links<T> {
+        pub const fn new() -> Self {
+            Links {
+                prev: Cell::new(None),
+                next: Cell::new(None),
+                list: Cell::new(0),
+                _pinned: PhantomPinned,
+            }
+        }
+
+        // returns true while the element is in a list
+        pub fn is_linked(&self) -> bool {
+            self.list.get() != 0
+        }
+    }
+
+    impl<T> Default for Links<T> {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    // Implemented by structs that embed a Links field.
+    // It is unsafe because the list trusts links to return the same field every time:
+    // handing out different Links for one element would corrupt the list.
+    #[allow(clippy::missing_safety_doc)]
+    pub unsafe trait Linked: Sized {
+        fn links(&self) -> &Links<Self>;
+    }
+
+    // every list gets a distinct id so an element knows which list it belongs to
+    static NEXT_LIST_ID: AtomicUsize = AtomicUsize::new(1);
+
+    // The linking shared by IntrusiveList and OwnedList, which only differ in whether
+    // the list borrows or owns the elements its pointers lead to.
+    struct RawList<T: Linked> {
+        id: usize,
+        head: Option<NonNull<T>>,
+        tail: Option<NonNull<T>>,
+        len: usize,
+    }
+
+    impl<T: Linked> RawList<T> {
+        fn new() -> Self {
+            RawList {
+                id: NEXT_LIST_ID.fetch_add(1, Ordering::Relaxed),
+                head: None,
+                tail: None,
+                len: 0,
+            }
+        }
+
+        fn contains(&self, item: &T) -> bool {
+            item.links().list.get() == self.id
+        }
+
+        // node must point to a pinned element that outlives its time in the list
+        // panics if it is already linked into a list
+        unsafe fn link_between(
+            &mut self,
+            node: NonNull<T>,
+            prev: Option<NonNull<T>>,
+            next: Option<NonNull<T>>,
+        ) {
+            let links = unsafe { node.as_ref() }.links();
+            if links.is_linked() {
+                panic!("element is already linked into a list");
+            }
+            links.prev.set(prev);
+            links.next.set(next);
+            links.list.set(self.id);
+            match prev {
+                Some(prev) => unsafe { prev.as_ref() }.links().next.set(Some(node)),
+                None => self.head = Some(node),
+            }
+            match next {
+                Some(next) => unsafe { next.as_ref() }.links().prev.set(Some(node)),
+                None => self.tail = Some(node),
+            }
+            self.len += 1;
+        }
+
+        unsafe fn push_back(&mut self, node: NonNull<T>) {
+            unsafe { self.link_between(node, self.tail, None) };
+        }
+
+        unsafe fn push_front(&mut self, node: NonNull<T>) {
+            unsafe { self.link_between(node, None, self.head) };
+        }
+
+        // node must be linked into this list
+        unsafe fn unlink(&mut self, node: NonNull<T>) {
+            let links = unsafe { node.as_ref() }.links();
+            let (prev, next) = (links.prev.take(), links.next.take());
+            match prev {
+                Some(prev) => unsafe { prev.as_ref() }.links().next.set(next),
+                None => self.head = next,
+            }
+            match next {
+                Some(next) => unsafe { next.as_ref() }.links().prev.set(prev),
+                None => self.tail = prev,
+            }
+            links.list.set(0);
+            self.len -= 1;
+        }
+
+        // unlinks and returns the first element, or None if the list is empty
+        fn pop_front(&mut self) -> Option<NonNull<T>> {
+            let node = self.head?;
+            unsafe { self.unlink(node) };
+            Some(node)
+        }
+
+        // unlinks and returns the last element, or None if the list is empty
+        fn pop_back(&mut self) -> Option<NonNull<T>> {
+            let node = self.tail?;
+            unsafe { self.unlink(node) };
+            Some(node)
+        }
+
+        fn iter<'a>(&self) -> Iter<'a, '_, T> {
+            Iter {
+                next: self.head,
+                _marker: PhantomData,
+            }
+        }
+    }
+
+    // A doubly linked list that threads through the Links embedded in its elements
+    // instead of allocating nodes. The list borrows each element for 'a, so the borrow
+    // checker keeps linked elements alive, and because they are pinned they cannot
+    // have moved either. Knowing an element is enough to unlink it in O(1).
+    // The elements have to be stored somewhere else and outlive the list, e.g. on the
+    // stack or in pinned boxes; OwnedList below is the variant that owns them instead.
+    pub struct IntrusiveList<'a, T: Linked> {
+        raw: RawList<T>,
+        _marker: PhantomData<Pin<&'a T>>,
+    }
+
+    impl<'a, T: Linked> IntrusiveList<'a, T> {
+        pub fn new() -> Self {
+            IntrusiveList {
+                raw: RawList::new(),
+                _marker: PhantomData,
+            }
+        }
+
+        pub fn len(&self) -> usize {
+            self.raw.len
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.raw.len == 0
+        }
+
+        pub fn front(&self) -> Option<Pin<&'a T>> {
+            self.raw.head.map(|node| unsafe { pinned(node) })
+        }
+
+        pub fn back(&self) -> Option<Pin<&'a T>> {
+            self.raw.tail.map(|node| unsafe { pinned(node) })
+        }
+
+        // links item at the back of the list
+        // panics if item is already linked into a list
+        pub fn push_back(&mut self, item: Pin<&'a T>) {
+            unsafe { self.raw.push_back(NonNull::from(item.get_ref())) };
+        }
+
+        // links item at the front of the list
+        // panics if item is already linked into a list
+        pub fn push_front(&mut self, item: Pin<&'a T>) {
+            unsafe { self.raw.push_front(NonNull::from(item.get_ref())) };
+        }
+
+        // unlinks and returns the first element, or None if the list is empty
+        pub fn pop_front(&mut self) -> Option<Pin<&'a T>> {
+            let node = self.raw.pop_front()?;
+            Some(unsafe { pinned(node) })
+        }
+
+        // unlinks and returns the last element, or None if the list is empty
+        pub fn pop_back(&mut self) -> Option<Pin<&'a T>> {
+            let node = self.raw.pop_back()?;
+            Some(unsafe { pinned(node) })
+        }
+
+        // returns true if item is linked into this list, in O(1)
+        pub fn contains(&self, item: &T) -> bool {
+            self.raw.contains(item)
+        }
+
+        // unlinks item in O(1) and returns true, or returns false if it is not in this list
+        pub fn remove(&mut self, item: Pin<&T>) -> bool {
+            if !self.contains(&item) {
+                return false;
+            }
+            unsafe { self.raw.unlink(NonNull::from(item.get_ref())) };
+            true
+        }
+
+        // moves an element of this list to the back, e.g. to mark it as most recently used
+        // returns false and does nothing if item is not in this list
+        pub fn move_to_back(&mut self, item: Pin<&'a T>) -> bool {
+            if !self.remove(item) {
+                return false;
+            }
+            self.push_back(item);
+            true
+        }
+
+        // iterates over the elements from front to back
+        pub fn iter(&self) -> Iter<'a, '_, T> {
+            self.raw.iter()
+        }
+
+        // unlinks every element, leaving them free to join another list
+        pub fn clear(&mut self) {
+            while self.pop_front().is_some() {}
+        }
+    }
+
+    // the elements outlive the list, so they are unlinked rather than dropped
+    impl<T: Linked> Drop for IntrusiveList<'_, T> {
+        fn drop(&mut self) {
+            self.clear();
+        }
+    }
+
+    impl<T: Linked> Default for IntrusiveList<'_, T> {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    // An intrusive list that owns its elements, each in the pinned box it was pushed in.
+    // Relinking or unlinking an element never allocates, so a cache can keep its entries
+    // in recency order with one allocation per entry and none per access. To find an
+    // entry again it keeps the pointer push_back or push_front returned, which stays
+    // valid until the entry is popped or removed and unlinks it in O(1).
+    pub struct OwnedList<T: Linked> {
+        raw: RawList<T>,
+        _marker: PhantomData<Pin<Box<T>>>,
+    }
+
+    impl<T: Linked> OwnedList<T> {
+        pub fn new() -> Self {
+            OwnedList {
+                raw: RawList::new(),
+                _marker: PhantomData,
+            }
+        }
+
+        pub fn len(&self) -> usize {
+            self.raw.len
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.raw.len == 0
+        }
+
+        pub fn front(&self) -> Option<Pin<&T>> {
+            self.raw.head.map(|node| unsafe { pinned(node) })
+        }
+
+        pub fn back(&self) -> Option<Pin<&T>> {
+            self.raw.tail.map(|node| unsafe { pinned(node) })
+        }
+
+        // links item at the back of the list and returns a pointer to it
+        pub fn push_back(&mut self, item: Pin<Box<T>>) -> NonNull<T> {
+            let node = into_raw(item);
+            unsafe { self.raw.push_back(node) };
+            node
+        }
+
+        // links item at the front of the list and returns a pointer to it
+        pub fn push_front(&mut self, item: Pin<Box<T>>) -> NonNull<T> {
+            let node = into_raw(item);
+            unsafe { self.raw.push_front(node) };
+            node
+        }
+
+        // unlinks the first element and hands it back, or returns None if the list is empty
+        pub fn pop_front(&mut self) -> Option<Pin<Box<T>>> {
+            let node = self.raw.pop_front()?;
+            Some(unsafe { from_raw(node) })
+        }
+
+        // unlinks the last element and hands it back, or returns None if the list is empty
+        pub fn pop_back(&mut self) -> Option<Pin<Box<T>>> {
+            let node = self.raw.pop_back()?;
+            Some(unsafe { from_raw(node) })
+        }
+
+        // returns true if item is linked into this list, in O(1)
+        pub fn contains(&self, item: &T) -> bool {
+            self.raw.contains(item)
+        }
+
+        // The methods taking a pointer require one returned by push_back or push_front
+        // of an OwnedList, whose element has not been popped or removed since.
+        // They do nothing when the element is in another list.
+
+        // unlinks the element in O(1) and hands it back, or returns None if it is not in this list
+        #[allow(clippy::missing_safety_doc)]
+        pub unsafe fn remove(&mut self, item: NonNull<T>) -> Option<Pin<Box<T>>> {
+            if !self.contains(unsafe { item.as_ref() }) {
+                return None;
+            }
+            unsafe {
+                self.raw.unlink(item);
+                Some(from_raw(item))
+            }
+        }
+
+        // moves the element to the back, e.g. to mark it as most recently used
+        // returns false if it is not in this list
+        #[allow(clippy::missing_safety_doc)]
+        pub unsafe fn move_to_back(&mut self, item: NonNull<T>) -> bool {
+            if !self.contains(unsafe { item.as_ref() }) {
+                return false;
+            }
+            unsafe {
+                self.raw.unlink(item);
+                self.raw.push_back(item);
+            }
+            true
+        }
+
+        // moves the element to the front, returning false if it is not in this list
+        #[allow(clippy::missing_safety_doc)]
+        pub unsafe fn move_to_front(&mut self, item: NonNull<T>) -> bool {
+            if !self.contains(unsafe { item.as_ref() }) {
+                return false;
+            }
+            unsafe {
+                self.raw.unlink(item);
+                self.raw.push_front(item);
+            }
+            true
+        }
+
+        // iterates over the elements from front to back
+        pub fn iter(&self) -> Iter<'_, '_, T> {
+            self.raw.iter()
+        }
+
+        // drops every element
+        pub fn clear(&mut self) {
+            while self.pop_front().is_some() {}
+        }
+    }
+
+    // the list owns its elements, so they are dropped with it
+    impl<T: Linked> Drop for OwnedList<T> {
+        fn drop(&mut self) {
+            self.clear();
+        }
+    }
+
+    impl<T: Linked> Default for OwnedList<T> {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    // the list links the pointer the box turns into, so that the element can be
+    // dropped through it once it is turned back into a box by from_raw
+    fn into_raw<T>(item: Pin<Box<T>>) -> NonNull<T> {
+        let item = unsafe { Pin::into_inner_unchecked(item) };
+        unsafe { NonNull::new_unchecked(Box::into_raw(item)) }
+    }
+
+    // node must come from into_raw, and no longer be linked
+    unsafe fn from_raw<T>(node: NonNull<T>) -> Pin<Box<T>> {
+        unsafe { Box::into_pin(Box::from_raw(node.as_ptr())) }
+    }
+
+    // node was pinned when it was linked, and the list's borrow keeps it alive for 'a
+    unsafe fn pinned<'a, T>(node: NonNull<T>) -> Pin<&'a T> {
+        unsafe { Pin::new_unchecked(&*node.as_ptr()) }
+    }
+
+    // borrows the list as well, so it cannot be changed while being iterated
+    pub struct Iter<'a, 'list, T> {
+        next: Option<NonNull<T>>,
+        _marker: PhantomData<(&'list (), Pin<&'a T>)>,
+    }
+
+    impl<'a, T: Linked> Iterator for Iter<'a, '_, T> {
+        type Item = Pin<&'a T>;
+
+        fn next(&mut self) -> Option<Pin<&'a T>> {
+            let node = self.next?;
+            let item = unsafe { pinned(node) };
+            self.next = item.links().next.get();
+            Some(item)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use std::pin::pin;
+
+        struct Timer {
+            deadline: u32,
+            links: Links<Timer>,
+        }
+
+        impl Timer {
+            fn new(deadline: u32) -> Self {
+                Timer {
+                    deadline,
+                    links: Links::new(),
+                }
+            }
+        }
+
+        unsafe impl Linked for Timer {
+            fn links(&self) -> &Links<Timer> {
+                &self.links
+            }
+        }
+
+        fn deadlines(list: &IntrusiveList<'_, Timer>) -> Vec<u32> {
+            list.iter().map(|timer| timer.deadline).collect()
+        }
+
+        #[test]
+        fn test_push_pop() {
+            let a = pin!(Timer::new(1));
+            let b = pin!(Timer::new(2));
+            let c = pin!(Timer::new(3));
+            let (a, b, c) = (a.into_ref(), b.into_ref(), c.into_ref());
+
+            let mut list = IntrusiveList::new();
+            assert!(list.is_empty());
+            list.push_back(b);
+            list.push_back(c);
+            list.push_front(a);
+            assert_eq!(list.len(), 3);
+            assert_eq!(deadlines(&list), [1, 2, 3]);
+            assert_eq!(list.front().map(|t| t.deadline), Some(1));
+            assert_eq!(list.back().map(|t| t.deadline), Some(3));
+
+            assert_eq!(list.pop_front().map(|t| t.deadline), Some(1));
+            assert_eq!(list.pop_back().map(|t| t.deadline), Some(3));
+            assert!(!a.links.is_linked());
+            assert!(b.links.is_linked());
+            assert_eq!(list.pop_back().map(|t| t.deadline), Some(2));
+            assert_eq!(list.pop_back().map(|t| t.deadline), None);
+        }
+
+        #[test]
+        fn test_remove_in_the_middle() {
+            let timers: Vec<Pin<Box<Timer>>> = (0..5).map(|i| Box::pin(Timer::new(i))).collect();
+            let mut list = IntrusiveList::new();
+            for timer in &timers {
+                list.push_back(timer.as_ref());
+            }
+
+            assert!(list.remove(timers[2].as_ref()));
+            assert!(!list.remove(timers[2].as_ref()));
+            assert!(list.remove(timers[0].as_ref()));
+            assert!(list.remove(timers[4].as_ref()));
+            assert_eq!(deadlines(&list), [1, 3]);
+            assert_eq!(list.len(), 2);
+
+            // a removed element can be linked again
+            list.push_front(timers[2].as_ref());
+            assert!(list.move_to_back(timers[1].as_ref()));
+            assert_eq!(deadlines(&list), [2, 3, 1]);
+        }
+
+        #[test]
+        fn test_elements_know_their_list() {
+            let a = pin!(Timer::new(1));
+            let b = pin!(Timer::new(2));
+            let (a, b) = (a.into_ref(), b.into_ref());
+
+            let mut first = IntrusiveList::new();
+            let mut second = IntrusiveList::new();
+            first.push_back(a);
+            second.push_back(b);
+
+            assert!(first.contains(&a));
+            assert!(!first.contains(&b));
+            // removing an element of another list leaves both lists untouched
+            assert!(!first.remove(b));
+            assert!(!first.move_to_back(b));
+            assert_eq!(deadlines(&second), [2]);
+
+            // dropping a list unlinks its elements so they can join another one
+            drop(first);
+            assert!(!a.links.is_linked());
+            second.push_back(a);
+            assert_eq!(deadlines(&second), [2, 1]);
+        }
+
+        #[test]
+        #[should_panic(expected = "already linked")]
+        fn test_double_insert_panics() {
+            let a = pin!(Timer::new(1));
+            let a = a.into_ref();
+            let mut list = IntrusiveList::new();
+            list.push_back(a);
+            list.push_front(a);
+        }
+
+        #[test]
+        fn test_owned_list() {
+            let mut list = OwnedList::new();
+            let one = list.push_back(Box::pin(Timer::new(1)));
+            let two = list.push_back(Box::pin(Timer::new(2)));
+            let zero = list.push_front(Box::pin(Timer::new(0)));
+            assert_eq!(list.len(), 3);
+            assert_eq!(
+                list.iter().map(|t| t.deadline).collect::<Vec<_>>(),
+                [0, 1, 2]
+            );
+
+            unsafe {
+                assert!(list.move_to_back(zero));
+                assert!(list.move_to_front(two));
+            }
+            assert_eq!(
+                list.iter().map(|t| t.deadline).collect::<Vec<_>>(),
+                [2, 1, 0]
+            );
+            assert_eq!(list.front().map(|t| t.deadline), Some(2));
+            assert_eq!(list.back().map(|t| t.deadline), Some(0));
+
+            let removed = unsafe { list.remove(one) }.unwrap();
+            assert_eq!(removed.deadline, 1);
+            assert!(!removed.links.is_linked());
+            // a removed element is an ordinary pinned box again and can be pushed back
+            let one = list.push_back(removed);
+            assert!(list.contains(unsafe { one.as_ref() }));
+
+            assert_eq!(list.pop_front().map(|t| t.deadline), Some(2));
+            assert_eq!(list.pop_back().map(|t| t.deadline), Some(1));
+            assert_eq!(list.len(), 1);
+        }
+
+        #[test]
+        fn test_owned_list_ignores_other_lists() {
+            let mut first = OwnedList::new();
+            let mut second = OwnedList::new();
+            let a = first.push_back(Box::pin(Timer::new(1)));
+            let b = second.push_back(Box::pin(Timer::new(2)));
+
+            unsafe {
+                assert!(first.remove(b).is_none());
+                assert!(!first.move_to_back(b));
+                assert!(!second.move_to_front(a));
+            }
+            assert_eq!(first.len(), 1);
+            assert_eq!(second.len(), 1);
+        }
+
+        #[test]
+        fn test_owned_list_drops_its_elements() {
+            use std::rc::Rc;
+
+            struct Counted {
+                drops: Rc<Cell<usize>>,
+                links: Links<Counted>,
+            }
+
+            impl Drop for Counted {
+                fn drop(&mut self) {
+                    self.drops.set(self.drops.get() + 1);
+                }
+            }
+
+            unsafe impl Linked for Counted {
+                fn links(&self) -> &Links<Counted> {
+                    &self.links
+                }
+            }
+
+            let drops = Rc::new(Cell::new(0));
+            let mut list = OwnedList::new();
+            for _ in 0..3 {
+                list.push_back(Box::pin(Counted {
+                    drops: drops.clone(),
+                    links: Links::new(),
+                }));
+            }
+            drop(list.pop_front());
+            assert_eq!(drops.get(), 1);
+            drop(list);
+            assert_eq!(drops.get(), 3);
+        }
+
+        #[test]
+        fn test_lru_bookkeeping() {
+            use std::collections::HashMap;
+
+            struct Entry {
+                key: u32,
+                value: &'static str,
+                links: Links<Entry>,
+            }
+
+            unsafe impl Linked for Entry {
+                fn links(&self) -> &Links<Entry> {
+                    &self.links
+                }
+            }
+
+            // the least recently used entry sits at the front, the most recent at the back
+            struct Lru {
+                map: HashMap<u32, NonNull<Entry>>,
+                order: OwnedList<Entry>,
+                capacity: usize,
+            }
+
+            impl Lru {
+                fn get(&mut self, key: u32) -> Option<&'static str> {
+                    let entry = *self.map.get(&key)?;
+                    unsafe {
+                        self.order.move_to_back(entry);
+                        Some(entry.as_ref().value)
+                    }
+                }
+
+                fn put(&mut self, key: u32, value: &'static str) {
+                    if let Some(entry) = self.map.remove(&key) {
+                        drop(unsafe { self.order.remove(entry) });
+                    } else if self.order.len() == self.capacity {
+                        let lru = self.order.pop_front().unwrap();
+                        self.map.remove(&lru.key);
+                    }
+                    let entry = Box::pin(Entry {
+                        key,
+                        value,
+                        links: Links::new(),
+                    });
+                    self.map.insert(key, self.order.push_back(entry));
+                }
+            }
+
+            let mut lru = Lru {
+                map: HashMap::new(),
+                order: OwnedList::new(),
+                capacity: 2,
+            };
+            lru.put(1, "one");
+            lru.put(2, "two");
+            assert_eq!(lru.get(1), Some("one"));
+            lru.put(3, "three");
+            assert_eq!(lru.get(2), None);
+            assert_eq!(lru.get(1), Some("one"));
+            lru.put(1, "uno");
+            lru.put(4, "four");
+            assert_eq!(lru.get(3), None);
+            assert_eq!(lru.get(1), Some("uno"));
+            assert_eq!(lru.get(4), Some("four"));
+        }
+    }
+}
@@ -1,23 +1,22 @@
 pub mod linked_list {
     use std::{
-        cell::RefCell,
-        fmt::{Debug, Display},
-        rc::Rc,
+        fmt,
+        hash::{Hash, Hasher},
+        marker::PhantomData,
+        ptr::{self, NonNull},
     };
 
     // Define the Node struct
-    #[derive(Clone)]
-    pub struct Node<T: Clone> {
+    // nodes are allocated with Box and linked through raw pointers, so the list
+    // is the single owner of every node and there are no reference cycles to leak
+    struct Node<T> {
         value: T,
-        next: Option<Rc<RefCell<Node<T>>>>,
-        prev: Option<Rc<RefCell<Node<T>>>>,
+        next: Option<NonNull<Node<T>>>,
+        prev: Option<NonNull<Node<T>>>,
     }
 
-    impl<T: Clone> Node<T>
-    where
-        T: Clone,
-    {
-        pub fn new(value: T) -> Self {
+    impl<T> Node<T> {
+        fn new(value: T) -> Self {
             Node {
                 value,
                 next: None,
@@ -27,214 +26,709 @@ pub mod linked_list {
     }
 
     // define the LinkedList struct
-    pub struct LinkedList<T: Clone> {
-        head: Option<Rc<RefCell<Node<T>>>>,
-        tail: Option<Rc<RefCell<Node<T>>>>,
+    pub struct LinkedList<T> {
+        head: Option<NonNull<Node<T>>>,
+        tail: Option<NonNull<Node<T>>>,
         len: usize,
+        // tells the drop checker that we own the boxed nodes
+        _marker: PhantomData<Box<Node<T>>>,
     }
 
-    impl<T: Clone + PartialEq + Debug + Display> LinkedList<T> {
+    unsafe impl<T: Send> Send for LinkedList<T> {}
+    unsafe impl<T: Sync> Sync for LinkedList<T> {}
+
+    // A pointer to one node of a LinkedList, for structures built on the list that
+    // need to reach an element in O(1) without walking to it, like the caches.
+    // It is only valid while its element is in the list it came from, which the
+    // unsafe methods taking a handle rely on.
+    pub(crate) struct NodeHandle<T>(NonNull<Node<T>>);
+
+    impl<T> NodeHandle<T> {
+        // returns a pointer to the element, which stays put while the node is in a list
+        // even when it is moved to another one; the node must still be alive
+        pub(crate) unsafe fn element(self) -> NonNull<T> {
+            unsafe { NonNull::new_unchecked(ptr::addr_of_mut!((*self.0.as_ptr()).value)) }
+        }
+
+        // returns a handle to the node after this one in its list; the node must be alive
+        pub(crate) unsafe fn next(self) -> Option<NodeHandle<T>> {
+            unsafe { (*self.0.as_ptr()).next.map(NodeHandle) }
+        }
+    }
+
+    impl<T> Clone for NodeHandle<T> {
+        fn clone(&self) -> Self {
+            *self
+        }
+    }
+
+    impl<T> Copy for NodeHandle<T> {}
+
+    impl<T> LinkedList<T> {
         pub fn new() -> Self {
             LinkedList {
                 head: None,
                 tail: None,
                 len: 0,
+                _marker: PhantomData,
             }
         }
 
         // return wheter the linked list is empty or not
-        pub fn is_empty(self) -> bool {
-            match self.len {
-                0 => return true,
-                _ => return false,
-            };
+        pub fn is_empty(&self) -> bool {
+            self.len == 0
         }
 
         // return the size of the linked list
-        pub fn len(self) -> usize {
+        pub fn len(&self) -> usize {
             self.len
         }
 
+        // returns a reference to the first element, or None if the list is empty
+        pub fn front(&self) -> Option<&T> {
+            self.head.map(|node| unsafe { &(*node.as_ptr()).value })
+        }
+
+        pub fn front_mut(&mut self) -> Option<&mut T> {
+            self.head.map(|node| unsafe { &mut (*node.as_ptr()).value })
+        }
+
+        // returns a reference to the last element, or None if the list is empty
+        pub fn back(&self) -> Option<&T> {
+            self.tail.map(|node| unsafe { &(*node.as_ptr()).value })
+        }
+
+        pub fn back_mut(&mut self) -> Option<&mut T> {
+            self.tail.map(|node| unsafe { &mut (*node.as_ptr()).value })
+        }
+
         // append a node at the end of the linked list
         pub fn push_back(&mut self, value: T) {
-            let new_node = Rc::new(RefCell::new(Node {
-                value: value.clone(),
-                prev: None,
-                next: None,
-            }));
+            unsafe { self.link_between(value, self.tail, None) };
+        }
+
+        // add an element first in the list
+        pub fn push_front(&mut self, value: T) {
+            unsafe { self.link_between(value, None, self.head) };
+        }
+
+        // removes the last element from a list and returns it, or None if it is empty
+        pub fn pop_back(&mut self) -> Option<T> {
+            self.tail.map(|node| unsafe { self.unlink(node).value })
+        }
+
+        // removes the first element from a list and returns it, or None if it is empty
+        pub fn pop_front(&mut self) -> Option<T> {
+            self.head.map(|node| unsafe { self.unlink(node).value })
+        }
 
-            match self.tail.take() {
-                Some(old_tail) => {
-                    old_tail.borrow_mut().next = Some(new_node.clone());
-                    new_node.borrow_mut().prev = Some(old_tail.clone());
-                    self.tail = Some(new_node.clone());
+        // removes the element at the given index and returns it
+        // walks from whichever end of the list is closer
+        pub fn remove(&mut self, index: usize) -> Option<T> {
+            if index >= self.len {
+                return None;
+            }
+
+            let node = if index < self.len / 2 {
+                let mut current = self.head.unwrap();
+                for _ in 0..index {
+                    current = unsafe { (*current.as_ptr()).next.unwrap() };
                 }
-                None => {
-                    self.head = Some(new_node.clone());
-                    self.tail = Some(new_node.clone());
+                current
+            } else {
+                let mut current = self.tail.unwrap();
+                for _ in index + 1..self.len {
+                    current = unsafe { (*current.as_ptr()).prev.unwrap() };
                 }
+                current
+            };
+
+            unsafe { Some(self.unlink(node).value) }
+        }
+
+        // append method to move all elements from other to the end of the list
+        pub fn append(&mut self, other: &mut Self) {
+            let Some(other_head) = other.head.take() else {
+                return;
+            };
+            match self.tail {
+                Some(old_tail) => unsafe {
+                    (*old_tail.as_ptr()).next = Some(other_head);
+                    (*other_head.as_ptr()).prev = Some(old_tail);
+                },
+                None => self.head = Some(other_head),
             }
+            self.tail = other.tail.take();
+            self.len += other.len;
+            other.len = 0;
+        }
 
-            self.len += 1;
+        // clear method to flush all the elements in the linked list
+        pub fn clear(&mut self) {
+            while self.pop_front().is_some() {}
         }
 
-        // add an element first in the list
-        pub fn push_front(&mut self, value: T) {
-            let new_node = Rc::new(RefCell::new(Node {
-                value: value.clone(),
-                prev: None,
-                next: None,
-            }));
+        // iterates over the elements from front to back
+        pub fn iter(&self) -> Iter<'_, T> {
+            Iter {
+                head: self.head,
+                tail: self.tail,
+                len: self.len,
+                _marker: PhantomData,
+            }
+        }
 
-            match self.head.take() {
-                Some(old_head) => {
-                    old_head.borrow_mut().prev = Some(new_node.clone());
-                    new_node.borrow_mut().next = Some(old_head.clone());
-                    self.head = Some(new_node);
-                }
-                None => {
-                    self.head = Some(new_node.clone());
-                    self.tail = Some(new_node.clone());
-                }
+        pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+            IterMut {
+                head: self.head,
+                tail: self.tail,
+                len: self.len,
+                _marker: PhantomData,
+            }
+        }
+
+        // returns a cursor pointing at the first element, or at the ghost position if the list is empty
+        pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T> {
+            CursorMut {
+                index: 0,
+                current: self.head,
+                list: self,
             }
+        }
+
+        // returns a cursor pointing at the last element, or at the ghost position if the list is empty
+        pub fn cursor_back_mut(&mut self) -> CursorMut<'_, T> {
+            CursorMut {
+                index: self.len.saturating_sub(1),
+                current: self.tail,
+                list: self,
+            }
+        }
+
+        // pushes value to the front and returns a handle to the node holding it
+        pub(crate) fn push_front_handle(&mut self, value: T) -> NodeHandle<T> {
+            self.push_front(value);
+            NodeHandle(self.head.unwrap())
+        }
+
+        // returns a handle to the first node, or None if the list is empty
+        pub(crate) fn front_handle(&self) -> Option<NodeHandle<T>> {
+            self.head.map(NodeHandle)
+        }
+
+        // returns a handle to the last node, or None if the list is empty
+        pub(crate) fn back_handle(&self) -> Option<NodeHandle<T>> {
+            self.tail.map(NodeHandle)
+        }
+
+        // inserts value right after the node of handle and returns a handle to its node
+        // handle must belong to this list
+        pub(crate) unsafe fn insert_after_handle(
+            &mut self,
+            handle: NodeHandle<T>,
+            value: T,
+        ) -> NodeHandle<T> {
+            unsafe {
+                let next = (*handle.0.as_ptr()).next;
+                self.link_between(value, Some(handle.0), next);
+                NodeHandle((*handle.0.as_ptr()).next.unwrap())
+            }
+        }
+
+        // removes the node of handle and returns its element
+        // handle must belong to this list
+        pub(crate) unsafe fn remove_handle(&mut self, handle: NodeHandle<T>) -> T {
+            unsafe { self.unlink(handle.0).value }
+        }
+
+        // moves the node of handle to the front, keeping the handle valid
+        // handle must belong to this list
+        pub(crate) unsafe fn move_to_front(&mut self, handle: NodeHandle<T>) {
+            unsafe { self.transfer_to_front(handle, None) };
+        }
+
+        // moves the node of handle to the front of other without reallocating it,
+        // so the handle stays valid and now belongs to other
+        // handle must belong to this list
+        pub(crate) unsafe fn move_to_front_of(&mut self, handle: NodeHandle<T>, other: &mut Self) {
+            unsafe { self.transfer_to_front(handle, Some(other)) };
+        }
 
+        // relinks node at the front of other, or of this list when other is None
+        unsafe fn transfer_to_front(&mut self, handle: NodeHandle<T>, other: Option<&mut Self>) {
+            let node = handle.0;
+            unsafe { self.detach(node) };
+            let target = other.unwrap_or(self);
+            unsafe { target.relink(None, node, node, target.head) };
+            target.len += 1;
+        }
+
+        // allocates a node for value and links it between two adjacent nodes,
+        // where None stands for the start or the end of the list
+        unsafe fn link_between(
+            &mut self,
+            value: T,
+            prev: Option<NonNull<Node<T>>>,
+            next: Option<NonNull<Node<T>>>,
+        ) {
+            let mut new_node = Box::new(Node::new(value));
+            new_node.prev = prev;
+            new_node.next = next;
+            let new_node = NonNull::from(Box::leak(new_node));
+            unsafe { self.relink(prev, new_node, new_node, next) };
             self.len += 1;
         }
 
-        // removes the last element from a list and returns it, or None if it is empty
-        pub fn pop_back(&mut self) -> Option<T> {
-            match self.tail.take() {
-                Some(old_tail) => {
-                    self.len -= 1;
-                    if let Some(prev_node) = old_tail.borrow_mut().prev.take() {
-                        prev_node.borrow_mut().next = None;
-                        self.tail = Some(prev_node);
-                    } else {
-                        self.head = None;
-                    }
-                    Some(old_tail.borrow().value.clone())
+        // points prev and next at the chain first..=last and the chain back at them
+        // the caller adjusts len
+        unsafe fn relink(
+            &mut self,
+            prev: Option<NonNull<Node<T>>>,
+            first: NonNull<Node<T>>,
+            last: NonNull<Node<T>>,
+            next: Option<NonNull<Node<T>>>,
+        ) {
+            unsafe {
+                (*first.as_ptr()).prev = prev;
+                (*last.as_ptr()).next = next;
+                match prev {
+                    Some(prev) => (*prev.as_ptr()).next = Some(first),
+                    None => self.head = Some(first),
+                }
+                match next {
+                    Some(next) => (*next.as_ptr()).prev = Some(last),
+                    None => self.tail = Some(last),
                 }
-                None => None,
             }
         }
 
-        // removes the first element from a list and returns it, or None if it is empty
-        pub fn pop_front(&mut self) -> Option<T> {
-            match self.head.take() {
-                Some(old_head) => {
-                    self.len -= 1;
-                    if let Some(next_node) = old_head.borrow_mut().next.take() {
-                        next_node.borrow_mut().prev = None;
-                        self.head = Some(next_node);
-                    } else {
-                        self.tail = None;
-                    }
-                    Some(old_head.borrow().value.clone())
-                }
-                None => None,
+        // detaches node from its neighbours and hands back ownership of it
+        // node must belong to this list
+        unsafe fn unlink(&mut self, node: NonNull<Node<T>>) -> Box<Node<T>> {
+            unsafe {
+                self.detach(node);
+                Box::from_raw(node.as_ptr())
+            }
+        }
+
+        // detaches node from its neighbours, leaving it allocated
+        // only goes through raw pointers, so pointers into the node stay usable
+        unsafe fn detach(&mut self, node: NonNull<Node<T>>) {
+            let (prev, next) = unsafe { ((*node.as_ptr()).prev, (*node.as_ptr()).next) };
+            match prev {
+                Some(prev) => unsafe { (*prev.as_ptr()).next = next },
+                None => self.head = next,
+            }
+            match next {
+                Some(next) => unsafe { (*next.as_ptr()).prev = prev },
+                None => self.tail = prev,
+            }
+            self.len -= 1;
+        }
+    }
+
+    // A cursor that can move over the list and edit it in place.
+    // Between the tail and the head sits a "ghost" position that points at no element:
+    // moving past either end lands there, and moving again wraps to the other end.
+    // Every edit at the cursor is O(1) since it only relinks the neighbouring nodes.
+    pub struct CursorMut<'a, T> {
+        // the position of current, or list.len at the ghost position
+        index: usize,
+        current: Option<NonNull<Node<T>>>,
+        list: &'a mut LinkedList<T>,
+    }
+
+    impl<T> CursorMut<'_, T> {
+        // returns the position of the cursor, or None at the ghost position
+        pub fn index(&self) -> Option<usize> {
+            self.current.map(|_| self.index)
+        }
+
+        // returns the element under the cursor, or None at the ghost position
+        pub fn current(&mut self) -> Option<&mut T> {
+            self.current
+                .map(|node| unsafe { &mut (*node.as_ptr()).value })
+        }
+
+        // returns the element the cursor would move to with move_next
+        pub fn peek_next(&mut self) -> Option<&mut T> {
+            self.next_node()
+                .map(|node| unsafe { &mut (*node.as_ptr()).value })
+        }
+
+        // returns the element the cursor would move to with move_prev
+        pub fn peek_prev(&mut self) -> Option<&mut T> {
+            self.prev_node()
+                .map(|node| unsafe { &mut (*node.as_ptr()).value })
+        }
+
+        // moves to the next element, from the tail to the ghost and from the ghost to the head
+        pub fn move_next(&mut self) {
+            self.current = self.next_node();
+            self.index = match self.current {
+                Some(_) if self.index < self.list.len => self.index + 1,
+                // coming from the ghost, which sits at list.len
+                Some(_) => 0,
+                None => self.list.len,
+            };
+        }
+
+        // moves to the previous element, from the head to the ghost and from the ghost to the tail
+        pub fn move_prev(&mut self) {
+            let at_ghost = self.current.is_none();
+            self.current = self.prev_node();
+            self.index = match self.current {
+                Some(_) if at_ghost => self.list.len - 1,
+                Some(_) => self.index - 1,
+                None => self.list.len,
+            };
+        }
+
+        // inserts value before the cursor, or at the back of the list at the ghost position
+        pub fn insert_before(&mut self, value: T) {
+            let (prev, next) = (self.prev_node(), self.current);
+            unsafe { self.list.link_between(value, prev, next) };
+            self.index += 1;
+        }
+
+        // inserts value after the cursor, or at the front of the list at the ghost position
+        pub fn insert_after(&mut self, value: T) {
+            let (prev, next) = (self.current, self.next_node());
+            unsafe { self.list.link_between(value, prev, next) };
+            if self.current.is_none() {
+                self.index += 1;
             }
         }
 
+        // removes the element under the cursor and moves the cursor to the next one
+        // returns None, removing nothing, at the ghost position
+        pub fn remove_current(&mut self) -> Option<T> {
+            let node = self.current?;
+            self.current = unsafe { (*node.as_ptr()).next };
+            let node = unsafe { self.list.unlink(node) };
+            Some(node.value)
+        }
+
+        // moves every element after the cursor into a new list and returns it
+        // at the ghost position that is the whole list
+        pub fn split_after(&mut self) -> LinkedList<T> {
+            let Some(first) = self.next_node() else {
+                return LinkedList::new();
+            };
+            let count = match self.current {
+                Some(_) => self.list.len - self.index - 1,
+                None => self.list.len,
+            };
+
+            let mut split = LinkedList::new();
+            split.head = Some(first);
+            split.tail = self.list.tail;
+            split.len = count;
+            unsafe { (*first.as_ptr()).prev = None };
+
+            match self.current {
+                Some(current) => unsafe { (*current.as_ptr()).next = None },
+                None => self.list.head = None,
+            }
+            self.list.tail = self.current;
+            self.list.len -= count;
+            if self.current.is_none() {
+                self.index = 0;
+            }
+            split
+        }
+
+        // moves every element before the cursor into a new list and returns it
+        // at the ghost position that is the whole list
+        pub fn split_before(&mut self) -> LinkedList<T> {
+            let Some(last) = self.prev_node() else {
+                return LinkedList::new();
+            };
+            // everything before the cursor, which at the ghost is the whole list
+            let count = self.index;
+
+            let mut split = LinkedList::new();
+            split.head = self.list.head;
+            split.tail = Some(last);
+            split.len = count;
+            unsafe { (*last.as_ptr()).next = None };
+
+            match self.current {
+                Some(current) => unsafe { (*current.as_ptr()).prev = None },
+                None => self.list.tail = None,
+            }
+            self.list.head = self.current;
+            self.list.len -= count;
+            self.index = 0;
+            split
+        }
+
+        // moves every element of other in after the cursor, or to the front at the ghost position
+        pub fn splice_after(&mut self, mut other: LinkedList<T>) {
+            let (Some(first), Some(last)) = (other.head.take(), other.tail.take()) else {
+                return;
+            };
+            let (prev, next) = (self.current, self.next_node());
+            unsafe { self.list.relink(prev, first, last, next) };
+            self.list.len += other.len;
+            if self.current.is_none() {
+                self.index += other.len;
+            }
+            // other no longer owns any nodes, so dropping it frees nothing
+            other.len = 0;
+        }
+
+        fn next_node(&self) -> Option<NonNull<Node<T>>> {
+            match self.current {
+                Some(node) => unsafe { (*node.as_ptr()).next },
+                None => self.list.head,
+            }
+        }
+
+        fn prev_node(&self) -> Option<NonNull<Node<T>>> {
+            match self.current {
+                Some(node) => unsafe { (*node.as_ptr()).prev },
+                None => self.list.tail,
+            }
+        }
+    }
+
+    impl<T: PartialEq> LinkedList<T> {
         // returns true if the linked list contains an element equal to the given value
-        pub fn contains(&self, value: T) -> bool {
-            let mut current = self.head.clone();
+        pub fn contains(&self, value: &T) -> bool {
+            self.iter().any(|el| el == value)
+        }
+    }
 
-            while let Some(node) = current {
-                if node.borrow().value == value {
-                    return true;
-                }
-                current = node.borrow().next.clone();
+    // The borrowing iterators walk inwards from both ends at once;
+    // len counts the elements left so the two ends never cross.
+
+    pub struct Iter<'a, T> {
+        head: Option<NonNull<Node<T>>>,
+        tail: Option<NonNull<Node<T>>>,
+        len: usize,
+        _marker: PhantomData<&'a Node<T>>,
+    }
+
+    impl<'a, T> Iterator for Iter<'a, T> {
+        type Item = &'a T;
+
+        fn next(&mut self) -> Option<&'a T> {
+            if self.len == 0 {
+                return None;
+            }
+            self.head.map(|node| {
+                let node = unsafe { &*node.as_ptr() };
+                self.len -= 1;
+                self.head = node.next;
+                &node.value
+            })
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            (self.len, Some(self.len))
+        }
+    }
+
+    impl<T> DoubleEndedIterator for Iter<'_, T> {
+        fn next_back(&mut self) -> Option<Self::Item> {
+            if self.len == 0 {
+                return None;
             }
+            self.tail.map(|node| {
+                let node = unsafe { &*node.as_ptr() };
+                self.len -= 1;
+                self.tail = node.prev;
+                &node.value
+            })
+        }
+    }
+
+    impl<T> ExactSizeIterator for Iter<'_, T> {}
 
-            false
+    impl<T> Clone for Iter<'_, T> {
+        fn clone(&self) -> Self {
+            Iter { ..*self }
         }
+    }
+
+    pub struct IterMut<'a, T> {
+        head: Option<NonNull<Node<T>>>,
+        tail: Option<NonNull<Node<T>>>,
+        len: usize,
+        _marker: PhantomData<&'a mut Node<T>>,
+    }
 
-        // print the list like an array
-        pub fn to_string(&self) -> String {
-            let mut current = self.head.clone();
-            let mut list_string = "[".to_string();
+    impl<'a, T> Iterator for IterMut<'a, T> {
+        type Item = &'a mut T;
 
-            while let Some(node) = current {
-                list_string.push_str(&format!("{:?}", node.borrow().value));
-                current = node.borrow().next.clone();
-                if current.is_some() {
-                    list_string.push_str(", ");
-                }
+        fn next(&mut self) -> Option<&'a mut T> {
+            if self.len == 0 {
+                return None;
             }
+            self.head.map(|node| {
+                let node = unsafe { &mut *node.as_ptr() };
+                self.len -= 1;
+                self.head = node.next;
+                &mut node.value
+            })
+        }
 
-            list_string.push_str("]");
-            list_string
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            (self.len, Some(self.len))
         }
+    }
 
-        // removes the element at the given index and returns it
-        pub fn remove(&mut self, index: usize) -> Option<T> {
-            if index >= self.len {
+    impl<T> DoubleEndedIterator for IterMut<'_, T> {
+        fn next_back(&mut self) -> Option<Self::Item> {
+            if self.len == 0 {
                 return None;
             }
+            self.tail.map(|node| {
+                let node = unsafe { &mut *node.as_ptr() };
+                self.len -= 1;
+                self.tail = node.prev;
+                &mut node.value
+            })
+        }
+    }
+
+    impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
+    // an iterator that moves the elements out of a list
+    pub struct IntoIter<T> {
+        list: LinkedList<T>,
+    }
+
+    impl<T> Iterator for IntoIter<T> {
+        type Item = T;
+
+        fn next(&mut self) -> Option<T> {
+            self.list.pop_front()
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            (self.list.len, Some(self.list.len))
+        }
+    }
 
-            let mut current = self.head.clone();
-            for _ in 0..index {
-                current = current.unwrap().borrow().next.clone();
+    impl<T> DoubleEndedIterator for IntoIter<T> {
+        fn next_back(&mut self) -> Option<T> {
+            self.list.pop_back()
+        }
+    }
+
+    impl<T> ExactSizeIterator for IntoIter<T> {}
+
+    impl<T> IntoIterator for LinkedList<T> {
+        type Item = T;
+        type IntoIter = IntoIter<T>;
+
+        fn into_iter(self) -> IntoIter<T> {
+            IntoIter { list: self }
+        }
+    }
+
+    impl<'a, T> IntoIterator for &'a LinkedList<T> {
+        type Item = &'a T;
+        type IntoIter = Iter<'a, T>;
+
+        fn into_iter(self) -> Iter<'a, T> {
+            self.iter()
+        }
+    }
+
+    impl<'a, T> IntoIterator for &'a mut LinkedList<T> {
+        type Item = &'a mut T;
+        type IntoIter = IterMut<'a, T>;
+
+        fn into_iter(self) -> IterMut<'a, T> {
+            self.iter_mut()
+        }
+    }
+
+    impl<T> FromIterator<T> for LinkedList<T> {
+        fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+            let mut list = LinkedList::new();
+            list.extend(iter);
+            list
+        }
+    }
+
+    impl<T> Extend<T> for LinkedList<T> {
+        fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+            for value in iter {
+                self.push_back(value);
             }
+        }
+    }
 
-            let node = current.unwrap();
-            let prev_node = node.borrow().prev.clone();
-            let next_node = node.borrow().next.clone();
+    impl<'a, T: Copy + 'a> Extend<&'a T> for LinkedList<T> {
+        fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+            self.extend(iter.into_iter().copied());
+        }
+    }
 
-            match (&prev_node, &next_node) {
-                (Some(prev), Some(next)) => {
-                    prev.borrow_mut().next = Some(next.clone());
-                    next.borrow_mut().prev = Some(prev.clone());
-                }
-                (None, Some(next)) => {
-                    next.borrow_mut().prev = None;
-                    self.head = Some(next.clone());
-                }
-                (Some(prev), None) => {
-                    prev.borrow_mut().next = None;
-                    self.tail = Some(prev.clone());
-                }
-                (None, None) => {
-                    self.head = None;
-                    self.tail = None;
-                }
+    impl<T: Clone> Clone for LinkedList<T> {
+        fn clone(&self) -> Self {
+            self.iter().cloned().collect()
+        }
+    }
+
+    impl<T: PartialEq> PartialEq for LinkedList<T> {
+        fn eq(&self, other: &Self) -> bool {
+            self.len == other.len && self.iter().eq(other)
+        }
+    }
+
+    impl<T: Eq> Eq for LinkedList<T> {}
+
+    impl<T: Hash> Hash for LinkedList<T> {
+        fn hash<H: Hasher>(&self, state: &mut H) {
+            // hashing the length keeps e.g. ([1], [2]) and ([1, 2], []) apart in a tuple
+            self.len.hash(state);
+            for value in self {
+                value.hash(state);
             }
+        }
+    }
 
-            self.len -= 1;
-            let x = Some(node.borrow().value.clone());
-            x
+    impl<T: fmt::Debug> fmt::Debug for LinkedList<T> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_list().entries(self).finish()
         }
+    }
 
-        // append method to move all elements from other to the end of the list
-        pub fn append(&mut self, other: &mut Self) {
-            match self.tail.take() {
-                Some(old_tail) => {
-                    old_tail.borrow_mut().next = other.head.take();
-                    self.tail = other.tail.take();
-                }
-                None => {
-                    self.head = other.head.take();
-                    self.tail = other.tail.take();
+    // print the list like an array
+    impl<T: fmt::Display> fmt::Display for LinkedList<T> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "[")?;
+            for (i, value) in self.iter().enumerate() {
+                if i > 0 {
+                    write!(f, ", ")?;
                 }
+                write!(f, "{}", value)?;
             }
-            self.len += other.len;
-            other.len = 0;
+            write!(f, "]")
         }
+    }
 
-        // clear method to flush all the elements in the linked list
-        pub fn clear(&mut self) {
-            self.head = None;
-            self.tail = None;
-            self.len = 0;
+    impl<T> Drop for LinkedList<T> {
+        fn drop(&mut self) {
+            self.clear();
+        }
+    }
+
+    impl<T> Default for LinkedList<T> {
+        fn default() -> Self {
+            Self::new()
         }
     }
 
     // From here on there are some tests to test my self-implemented methods
     #[cfg(test)]
     mod tests {
-        //use std::collections::linked_list;
-
-        use std::{cell::RefCell, rc::Rc};
+        use std::{cell::Cell, rc::Rc};
 
         use crate::data_structures::linked_list::linked_list::{LinkedList, Node};
 
@@ -260,19 +754,23 @@ pub mod linked_list {
 
             list.push_back(5);
             assert_eq!(list.len, 1);
-            assert_eq!(list.head.as_ref().unwrap().borrow().value, 5);
-            assert_eq!(list.tail.as_ref().unwrap().borrow().value, 5);
+            assert_eq!(list.front(), Some(&5));
+            assert_eq!(list.back(), Some(&5));
 
             list.push_back(10);
             assert_eq!(list.len, 2);
-            assert_eq!(list.head.as_ref().unwrap().borrow().value, 5);
-            assert_eq!(list.tail.as_ref().unwrap().borrow().value, 10);
+            assert_eq!(list.front(), Some(&5));
+            assert_eq!(list.back(), Some(&10));
         }
 
         #[test]
         fn test_is_empty_linked_list() {
-            let ll = LinkedList::<i32>::new();
-            assert_eq!(ll.is_empty(), true);
+            let mut ll = LinkedList::<i32>::new();
+            assert!(ll.is_empty());
+            ll.push_back(1);
+            // the accessors borrow, so the list is still usable afterwards
+            assert!(!ll.is_empty());
+            assert_eq!(ll.len(), 1);
         }
 
         #[test]
@@ -288,13 +786,12 @@ pub mod linked_list {
             let mut ll = LinkedList::<i32>::new();
             ll.push_back(5);
             ll.push_front(2);
-            assert_eq!(ll.head.as_ref().unwrap().borrow().value, 2);
+            assert_eq!(ll.front(), Some(&2));
             ll.push_back(3);
-            let tail_node = ll.tail.as_ref().unwrap();
-            let binding = tail_node.borrow();
-            let prev_node = binding.prev.as_ref().unwrap();
-            assert_eq!(prev_node.borrow().value, 5);
-            assert_eq!(ll.tail.as_ref().unwrap().borrow().value, 3);
+            let tail_node = unsafe { &*ll.tail.unwrap().as_ptr() };
+            let prev_node = unsafe { &*tail_node.prev.unwrap().as_ptr() };
+            assert_eq!(prev_node.value, 5);
+            assert_eq!(ll.back(), Some(&3));
         }
 
         #[test]
@@ -305,7 +802,7 @@ pub mod linked_list {
 
             assert_eq!(ll.pop_back(), Some(10));
             assert_eq!(ll.len, 1);
-            assert_eq!(ll.tail.as_ref().unwrap().borrow().value, 5);
+            assert_eq!(ll.back(), Some(&5));
             assert_eq!(ll.pop_back(), Some(5));
             assert_eq!(ll.len, 0);
             assert!(ll.tail.is_none());
@@ -320,13 +817,24 @@ pub mod linked_list {
 
             assert_eq!(ll.pop_front(), Some(10));
             assert_eq!(ll.len, 1);
-            assert_eq!(ll.head.as_ref().unwrap().borrow().value, 5);
+            assert_eq!(ll.front(), Some(&5));
             assert_eq!(ll.pop_front(), Some(5));
             assert_eq!(ll.len, 0);
             assert!(ll.tail.is_none());
             assert_eq!(ll.pop_front(), None);
         }
 
+        #[test]
+        fn test_front_back_mut() {
+            let mut ll = LinkedList::new();
+            assert_eq!(ll.front_mut(), None);
+            ll.push_back(1);
+            ll.push_back(2);
+            *ll.front_mut().unwrap() += 10;
+            *ll.back_mut().unwrap() *= 10;
+            assert_eq!(ll.to_string(), "[11, 20]");
+        }
+
         #[test]
         fn test_contains() {
             let mut list = LinkedList::<i32>::new();
@@ -334,10 +842,9 @@ pub mod linked_list {
             list.push_back(5);
             list.push_back(10);
             list.push_back(20);
-            assert!(list.contains(5));
-            assert!(list.contains(10));
-            //println!("{:?}", list.to_string());
-            assert!(!list.contains(15));
+            assert!(list.contains(&5));
+            assert!(list.contains(&10));
+            assert!(!list.contains(&15));
         }
 
         #[test]
@@ -350,13 +857,13 @@ pub mod linked_list {
 
             assert_eq!(list.remove(1), Some(10));
             assert_eq!(list.len, 2);
-            assert_eq!(list.head.as_ref().unwrap().borrow().value, 5);
-            assert_eq!(list.tail.as_ref().unwrap().borrow().value, 15);
+            assert_eq!(list.front(), Some(&5));
+            assert_eq!(list.back(), Some(&15));
 
             assert_eq!(list.remove(0), Some(5));
             assert_eq!(list.len, 1);
-            assert_eq!(list.head.as_ref().unwrap().borrow().value, 15);
-            assert_eq!(list.tail.as_ref().unwrap().borrow().value, 15);
+            assert_eq!(list.front(), Some(&15));
+            assert_eq!(list.back(), Some(&15));
 
             assert_eq!(list.remove(0), Some(15));
             assert_eq!(list.len, 0);
@@ -366,13 +873,22 @@ pub mod linked_list {
             assert_eq!(list.remove(0), None);
         }
 
+        #[test]
+        fn test_remove_from_either_end() {
+            let mut list = LinkedList::new();
+            for i in 0..10 {
+                list.push_back(i);
+            }
+            assert_eq!(list.remove(8), Some(8));
+            assert_eq!(list.remove(1), Some(1));
+            assert_eq!(list.to_string(), "[0, 2, 3, 4, 5, 6, 7, 9]");
+        }
+
         #[test]
         fn test_clear() {
-            let mut list = LinkedList {
-                head: Some(Rc::new(RefCell::new(Node::new(1)))),
-                tail: Some(Rc::new(RefCell::new(Node::new(2)))),
-                len: 2,
-            };
+            let mut list = LinkedList::new();
+            list.push_back(1);
+            list.push_back(2);
             list.clear();
             assert!(list.head.is_none());
             assert!(list.tail.is_none());
@@ -381,19 +897,275 @@ pub mod linked_list {
 
         #[test]
         fn test_append() {
-            let mut list1 = LinkedList {
-                head: Some(Rc::new(RefCell::new(Node::new(1)))),
-                tail: Some(Rc::new(RefCell::new(Node::new(2)))),
-                len: 2,
-            };
-            let mut list2 = LinkedList {
-                head: Some(Rc::new(RefCell::new(Node::new(3)))),
-                tail: Some(Rc::new(RefCell::new(Node::new(4)))),
-                len: 2,
-            };
+            let mut list1 = LinkedList::new();
+            list1.push_back(1);
+            list1.push_back(2);
+            let mut list2 = LinkedList::new();
+            list2.push_back(3);
+            list2.push_back(4);
             list1.append(&mut list2);
             assert_eq!(list1.len, 4);
             assert_eq!(list2.len, 0);
+            assert!(list2.head.is_none());
+
+            // the links run both ways across the join
+            assert_eq!(list1.pop_back(), Some(4));
+            assert_eq!(list1.pop_back(), Some(3));
+            assert_eq!(list1.pop_back(), Some(2));
+            assert_eq!(list1.to_string(), "[1]");
+        }
+
+        #[test]
+        fn test_no_trait_bounds_and_drop() {
+            // neither Clone, PartialEq, Debug nor Display is needed
+            struct DropCounter(Rc<Cell<usize>>);
+            impl Drop for DropCounter {
+                fn drop(&mut self) {
+                    self.0.set(self.0.get() + 1);
+                }
+            }
+
+            let drops = Rc::new(Cell::new(0));
+            let mut list = LinkedList::new();
+            for _ in 0..5 {
+                list.push_back(DropCounter(drops.clone()));
+            }
+            drop(list.pop_front());
+            drop(list.remove(2));
+            assert_eq!(drops.get(), 2);
+            drop(list);
+            assert_eq!(drops.get(), 5);
+        }
+
+        fn list_of(values: &[i32]) -> LinkedList<i32> {
+            let mut list = LinkedList::new();
+            for &value in values {
+                list.push_back(value);
+            }
+            list
+        }
+
+        #[test]
+        fn test_cursor_moves_and_wraps() {
+            let mut list = list_of(&[1, 2, 3]);
+            let mut cursor = list.cursor_front_mut();
+            assert_eq!(cursor.index(), Some(0));
+            assert_eq!(cursor.current(), Some(&mut 1));
+            assert_eq!(cursor.peek_prev(), None);
+
+            cursor.move_next();
+            cursor.move_next();
+            assert_eq!(cursor.index(), Some(2));
+            assert_eq!(cursor.peek_next(), None);
+
+            // past the tail is the ghost, and past the ghost is the head again
+            cursor.move_next();
+            assert_eq!(cursor.index(), None);
+            assert_eq!(cursor.current(), None);
+            assert_eq!(cursor.peek_next(), Some(&mut 1));
+            assert_eq!(cursor.peek_prev(), Some(&mut 3));
+            cursor.move_next();
+            assert_eq!(cursor.index(), Some(0));
+
+            cursor.move_prev();
+            assert_eq!(cursor.index(), None);
+            cursor.move_prev();
+            assert_eq!(cursor.index(), Some(2));
+            *cursor.current().unwrap() = 30;
+            assert_eq!(list.to_string(), "[1, 2, 30]");
+
+            let mut empty = LinkedList::<i32>::new();
+            let mut cursor = empty.cursor_back_mut();
+            assert_eq!(cursor.index(), None);
+            cursor.move_next();
+            cursor.move_prev();
+            assert_eq!(cursor.current(), None);
+        }
+
+        #[test]
+        fn test_cursor_insert() {
+            let mut list = list_of(&[1, 3]);
+            let mut cursor = list.cursor_front_mut();
+            cursor.insert_after(2);
+            cursor.insert_before(0);
+            assert_eq!(cursor.index(), Some(1));
+            assert_eq!(cursor.current(), Some(&mut 1));
+            assert_eq!(cursor.peek_next(), Some(&mut 2));
+
+            cursor.move_prev();
+            cursor.move_prev();
+            // the ghost inserts at the back before it and at the front after it
+            cursor.insert_before(4);
+            cursor.insert_after(-1);
+            assert_eq!(cursor.index(), None);
+            cursor.move_next();
+            assert_eq!(cursor.index(), Some(0));
+            assert_eq!(list.to_string(), "[-1, 0, 1, 2, 3, 4]");
+            assert_eq!(list.len(), 6);
+            assert_eq!(list.back(), Some(&4));
+        }
+
+        #[test]
+        fn test_cursor_remove_current() {
+            let mut list = list_of(&[1, 2, 3, 4]);
+            let mut cursor = list.cursor_front_mut();
+            cursor.move_next();
+            assert_eq!(cursor.remove_current(), Some(2));
+            assert_eq!(cursor.current(), Some(&mut 3));
+            assert_eq!(cursor.index(), Some(1));
+
+            cursor.move_next();
+            assert_eq!(cursor.remove_current(), Some(4));
+            assert_eq!(cursor.index(), None);
+            assert_eq!(cursor.remove_current(), None);
+            cursor.move_next();
+            assert_eq!(cursor.remove_current(), Some(1));
+            assert_eq!(list.to_string(), "[3]");
+            assert_eq!(list.front(), list.back());
+        }
+
+        #[test]
+        fn test_cursor_split() {
+            let mut list = list_of(&[1, 2, 3, 4, 5]);
+            let mut cursor = list.cursor_front_mut();
+            cursor.move_next();
+            cursor.move_next();
+            let after = cursor.split_after();
+            assert_eq!(cursor.index(), Some(2));
+            let before = cursor.split_before();
+            assert_eq!(cursor.index(), Some(0));
+            assert_eq!(cursor.current(), Some(&mut 3));
+
+            assert_eq!(after.to_string(), "[4, 5]");
+            assert_eq!(after.len(), 2);
+            assert_eq!(before.to_string(), "[1, 2]");
+            assert_eq!(before.back(), Some(&2));
+            assert_eq!(list.to_string(), "[3]");
+            assert_eq!(list.len(), 1);
+
+            // at the ghost both splits take the whole list
+            let mut list = list_of(&[1, 2]);
+            let mut cursor = list.cursor_back_mut();
+            cursor.move_next();
+            assert_eq!(cursor.split_after().to_string(), "[1, 2]");
+            assert_eq!(cursor.index(), None);
+            assert!(list.is_empty());
+        }
+
+        #[test]
+        fn test_cursor_splice_after() {
+            let mut list = list_of(&[1, 5]);
+            let mut cursor = list.cursor_front_mut();
+            cursor.splice_after(list_of(&[2, 3, 4]));
+            assert_eq!(cursor.index(), Some(0));
+            cursor.splice_after(LinkedList::new());
+            cursor.move_prev();
+            cursor.splice_after(list_of(&[-1, 0]));
+            assert_eq!(cursor.index(), None);
+            cursor.move_prev();
+            assert_eq!(cursor.current(), Some(&mut 5));
+            assert_eq!(cursor.index(), Some(6));
+
+            assert_eq!(list.to_string(), "[-1, 0, 1, 2, 3, 4, 5]");
+            assert_eq!(list.len(), 7);
+            // walk backwards to check the prev links across both joins
+            let mut backwards = Vec::new();
+            while let Some(value) = list.pop_back() {
+                backwards.push(value);
+            }
+            assert_eq!(backwards, [5, 4, 3, 2, 1, 0, -1]);
+        }
+
+        #[test]
+        fn test_iter() {
+            let list = list_of(&[1, 2, 3, 4]);
+            assert_eq!(list.iter().copied().collect::<Vec<_>>(), [1, 2, 3, 4]);
+            assert_eq!(list.iter().rev().copied().collect::<Vec<_>>(), [4, 3, 2, 1]);
+
+            // the two ends meet in the middle without yielding anything twice
+            let mut iter = list.iter();
+            assert_eq!(iter.len(), 4);
+            assert_eq!(iter.next(), Some(&1));
+            assert_eq!(iter.next_back(), Some(&4));
+            assert_eq!(iter.next_back(), Some(&3));
+            assert_eq!(iter.next(), Some(&2));
+            assert_eq!(iter.next(), None);
+            assert_eq!(iter.next_back(), None);
+
+            let mut sum = 0;
+            for value in &list {
+                sum += value;
+            }
+            assert_eq!(sum, 10);
+        }
+
+        #[test]
+        fn test_iter_mut() {
+            let mut list = list_of(&[1, 2, 3]);
+            for value in list.iter_mut() {
+                *value *= 10;
+            }
+            for value in &mut list {
+                *value += 1;
+            }
+            if let Some(last) = list.iter_mut().next_back() {
+                *last = 0;
+            }
+            assert_eq!(list.to_string(), "[11, 21, 0]");
+        }
+
+        #[test]
+        fn test_into_iter() {
+            let list: LinkedList<String> = ["a", "b", "c"].iter().map(|s| s.to_string()).collect();
+            let mut iter = list.into_iter();
+            assert_eq!(iter.next_back().as_deref(), Some("c"));
+            assert_eq!(iter.len(), 2);
+            assert_eq!(iter.collect::<Vec<_>>(), ["a", "b"]);
+        }
+
+        #[test]
+        fn test_collect_extend_clone_eq() {
+            let mut list: LinkedList<i32> = (1..=3).collect();
+            list.extend([4, 5]);
+            list.extend(&[6]);
+            assert_eq!(list.len(), 6);
+
+            let copy = list.clone();
+            assert_eq!(copy, list);
+            assert_eq!(copy, (1..=6).collect());
+            assert_ne!(copy, (1..=5).collect());
+            assert_ne!(copy, (0..6).collect());
+        }
+
+        #[test]
+        fn test_hash() {
+            use std::{
+                collections::hash_map::DefaultHasher,
+                hash::{Hash, Hasher},
+            };
+
+            fn hash_of<T: Hash>(value: &T) -> u64 {
+                let mut hasher = DefaultHasher::new();
+                value.hash(&mut hasher);
+                hasher.finish()
+            }
+
+            let a: LinkedList<i32> = (0..5).collect();
+            let b: LinkedList<i32> = (0..5).collect();
+            assert_eq!(hash_of(&a), hash_of(&b));
+            assert_ne!(hash_of(&a), hash_of(&(0..4).collect::<LinkedList<i32>>()));
+
+            let split = (list_of(&[1]), list_of(&[2]));
+            let joined = (list_of(&[1, 2]), LinkedList::<i32>::new());
+            assert_ne!(hash_of(&split), hash_of(&joined));
+        }
+
+        #[test]
+        fn test_debug_and_display() {
+            let words: LinkedList<&str> = ["x", "y"].into_iter().collect();
+            assert_eq!(format!("{:?}", words), r#"["x", "y"]"#);
+            assert_eq!(format!("{}", words), "[x, y]");
+            assert_eq!(LinkedList::<i32>::new().to_string(), "[]");
         }
     }
 }
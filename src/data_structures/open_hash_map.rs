@@ -0,0 +1,395 @@
+pub mod open_hash_map {
+    use std::{
+        borrow::Borrow,
+        collections::hash_map::RandomState,
+        hash::{BuildHasher, Hash},
+        mem,
+    };
+
+    // the table grows once it is more than 7/8 full
+    const MAX_LOAD_NUMERATOR: usize = 7;
+    const MAX_LOAD_DENOMINATOR: usize = 8;
+    const MIN_CAPACITY: usize = 8;
+
+    // A hash map using open addressing with Robin Hood linear probing.
+    // Every entry lives directly in the slot array, so a lookup is a short scan
+    // over contiguous memory instead of a walk through a chain of list nodes.
+    // On insertion an entry that is further from its home slot steals the place
+    // of a "richer" entry closer to home, which keeps probe sequences short and
+    // lets a lookup stop as soon as it meets an entry richer than the key it seeks.
+    pub struct OpenHashMap<K, V, S = RandomState> {
+        slots: Vec<Option<Slot<K, V>>>,
+        len: usize,
+        hash_builder: S,
+    }
+
+    struct Slot<K, V> {
+        hash: u64,
+        key: K,
+        value: V,
+    }
+
+    impl<K: Eq + Hash, V> OpenHashMap<K, V> {
+        // creates a map with room for at least size slots, rounded up to a power of two
+        pub fn new(size: usize) -> Self {
+            Self::with_size_and_hasher(size, RandomState::new())
+        }
+    }
+
+    impl<K: Eq + Hash, V, S: BuildHasher> OpenHashMap<K, V, S> {
+        // creates an empty map that hashes its keys with hash_builder
+        pub fn with_hasher(hash_builder: S) -> Self {
+            Self::with_size_and_hasher(0, hash_builder)
+        }
+
+        pub fn with_size_and_hasher(size: usize, hash_builder: S) -> Self {
+            let capacity = if size == 0 {
+                0
+            } else {
+                size.max(MIN_CAPACITY).next_power_of_two()
+            };
+            Self {
+                slots: empty_slots(capacity),
+                len: 0,
+                hash_builder,
+            }
+        }
+
+        // inserts a key-value pair and returns the value previously stored under key, if any
+        pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+            let hash = self.hash_builder.hash_one(&key);
+            if let Some(index) = self.find_index(hash, &key) {
+                let slot = self.slots[index].as_mut().unwrap();
+                return Some(mem::replace(&mut slot.value, value));
+            }
+
+            if (self.len + 1) * MAX_LOAD_DENOMINATOR > self.slots.len() * MAX_LOAD_NUMERATOR {
+                self.grow();
+            }
+            self.insert_new(Slot { hash, key, value });
+            self.len += 1;
+            None
+        }
+
+        pub fn search<Q>(&self, key: &Q) -> Option<&V>
+        where
+            K: Borrow<Q>,
+            Q: Hash + Eq + ?Sized,
+        {
+            let index = self.find_index(self.hash_builder.hash_one(key), key)?;
+            self.slots[index].as_ref().map(|slot| &slot.value)
+        }
+
+        pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+        where
+            K: Borrow<Q>,
+            Q: Hash + Eq + ?Sized,
+        {
+            let index = self.find_index(self.hash_builder.hash_one(key), key)?;
+            self.slots[index].as_mut().map(|slot| &mut slot.value)
+        }
+
+        // removes the key and shifts the following entries of its probe run one slot back,
+        // so no tombstones are ever left behind
+        // returns the removed value, or None if the key was not in the map
+        pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+        where
+            K: Borrow<Q>,
+            Q: Hash + Eq + ?Sized,
+        {
+            let mut index = self.find_index(self.hash_builder.hash_one(key), key)?;
+            let removed = self.slots[index].take().unwrap();
+            self.len -= 1;
+
+            let mask = self.slots.len() - 1;
+            loop {
+                let next = (index + 1) & mask;
+                match &self.slots[next] {
+                    Some(slot) if self.probe_distance(slot.hash, next) > 0 => {
+                        self.slots[index] = self.slots[next].take();
+                        index = next;
+                    }
+                    _ => break,
+                }
+            }
+            Some(removed.value)
+        }
+
+        pub fn delete<Q>(&mut self, key: &Q)
+        where
+            K: Borrow<Q>,
+            Q: Hash + Eq + ?Sized,
+        {
+            self.remove(key);
+        }
+
+        pub fn contains_key<Q>(&self, key: &Q) -> bool
+        where
+            K: Borrow<Q>,
+            Q: Hash + Eq + ?Sized,
+        {
+            self.search(key).is_some()
+        }
+
+        pub fn get<Q>(&self, key: &Q) -> Option<&V>
+        where
+            K: Borrow<Q>,
+            Q: Hash + Eq + ?Sized,
+        {
+            self.search(key)
+        }
+
+        pub fn clear(&mut self) {
+            for slot in &mut self.slots {
+                *slot = None;
+            }
+            self.len = 0;
+        }
+
+        pub fn len(&self) -> usize {
+            self.len
+        }
+
+        // returns the number of slots in the table
+        pub fn capacity(&self) -> usize {
+            self.slots.len()
+        }
+
+        pub fn keys(&self) -> impl Iterator<Item = &K> {
+            self.iter().map(|(key, _)| key)
+        }
+
+        pub fn values(&self) -> impl Iterator<Item = &V> {
+            self.iter().map(|(_, value)| value)
+        }
+
+        pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+            self.slots
+                .iter()
+                .flatten()
+                .map(|slot| (&slot.key, &slot.value))
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.len == 0
+        }
+
+        pub fn size(&self) -> usize {
+            self.len()
+        }
+
+        // how far the entry stored at index is from the slot its hash points to
+        fn probe_distance(&self, hash: u64, index: usize) -> usize {
+            let mask = self.slots.len() - 1;
+            index.wrapping_sub(hash as usize) & mask
+        }
+
+        fn find_index<Q>(&self, hash: u64, key: &Q) -> Option<usize>
+        where
+            K: Borrow<Q>,
+            Q: Eq + ?Sized,
+        {
+            if self.slots.is_empty() {
+                return None;
+            }
+            let mask = self.slots.len() - 1;
+            let mut index = hash as usize & mask;
+            let mut distance = 0;
+            while let Some(slot) = &self.slots[index] {
+                // a richer entry means our key would have been placed before it
+                if self.probe_distance(slot.hash, index) < distance {
+                    return None;
+                }
+                if slot.hash == hash && slot.key.borrow() == key {
+                    return Some(index);
+                }
+                index = (index + 1) & mask;
+                distance += 1;
+            }
+            None
+        }
+
+        // places an entry known not to be in the table, assuming there is a free slot
+        fn insert_new(&mut self, mut entry: Slot<K, V>) {
+            let mask = self.slots.len() - 1;
+            let mut index = entry.hash as usize & mask;
+            let mut distance = 0;
+            loop {
+                let resident_distance = match &self.slots[index] {
+                    None => {
+                        self.slots[index] = Some(entry);
+                        return;
+                    }
+                    Some(slot) => self.probe_distance(slot.hash, index),
+                };
+                if resident_distance < distance {
+                    // steal from the rich: the resident continues probing in our place
+                    mem::swap(self.slots[index].as_mut().unwrap(), &mut entry);
+                    distance = resident_distance;
+                }
+                index = (index + 1) & mask;
+                distance += 1;
+            }
+        }
+
+        // doubles the table, moving every entry with its cached hash
+        fn grow(&mut self) {
+            let new_capacity = (self.slots.len() * 2).max(MIN_CAPACITY);
+            let old_slots = mem::replace(&mut self.slots, empty_slots(new_capacity));
+            for slot in old_slots.into_iter().flatten() {
+                self.insert_new(slot);
+            }
+        }
+    }
+
+    fn empty_slots<K, V>(capacity: usize) -> Vec<Option<Slot<K, V>>> {
+        (0..capacity).map(|_| None).collect()
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::data_structures::hasher::hasher::FxBuildHasher;
+
+        #[test]
+        fn test_new() {
+            let map: OpenHashMap<i32, i32> = OpenHashMap::new(10);
+            assert_eq!(map.capacity(), 16);
+            assert_eq!(map.len(), 0);
+
+            let map: OpenHashMap<i32, i32> = OpenHashMap::new(0);
+            assert_eq!(map.capacity(), 0);
+            assert_eq!(map.search(&1), None);
+        }
+
+        #[test]
+        fn test_insert_and_search() {
+            let mut map: OpenHashMap<i32, i32> = OpenHashMap::new(10);
+            map.insert(1, 2);
+            map.insert(3, 4);
+            map.insert(5, 6);
+            map.insert(5, 7);
+
+            assert_eq!(map.len(), 3);
+            assert_eq!(map.search(&1), Some(&2));
+            assert_eq!(map.search(&3), Some(&4));
+            assert_eq!(map.search(&5), Some(&7));
+            assert_eq!(map.search(&8), None);
+        }
+
+        #[test]
+        fn test_insert_remove_and_get_mut_return_values() {
+            let mut map: OpenHashMap<i32, Box<str>> = OpenHashMap::new(0);
+            assert_eq!(map.insert(1, "one".into()), None);
+            assert_eq!(map.insert(1, "uno".into()), Some("one".into()));
+
+            map.get_mut(&1).unwrap().make_ascii_uppercase();
+            assert_eq!(map.remove(&1), Some("UNO".into()));
+            assert_eq!(map.remove(&1), None);
+            assert!(map.get_mut(&1).is_none());
+        }
+
+        #[test]
+        fn test_grows_from_zero() {
+            let mut map: OpenHashMap<u32, u32> = OpenHashMap::new(0);
+            for i in 0..1_000 {
+                map.insert(i, i * 2);
+            }
+
+            assert_eq!(map.len(), 1_000);
+            assert!(map.len() * 8 <= map.capacity() * 7);
+            for i in 0..1_000 {
+                assert_eq!(map.search(&i), Some(&(i * 2)));
+            }
+        }
+
+        #[test]
+        fn test_delete_shifts_probe_runs_back() {
+            let mut map: OpenHashMap<u32, u32> = OpenHashMap::new(16);
+            for i in 0..500 {
+                map.insert(i, i);
+            }
+            for i in (0..500).filter(|i| i % 3 == 0) {
+                map.delete(&i);
+            }
+            map.delete(&1_000);
+
+            assert_eq!(map.len(), 333);
+            for i in 0..500 {
+                assert_eq!(map.contains_key(&i), i % 3 != 0);
+            }
+            // no entry may sit further from home than the one before it allows
+            for (index, slot) in map.slots.iter().enumerate() {
+                if let Some(slot) = slot {
+                    let distance = map.probe_distance(slot.hash, index);
+                    if distance > 0 {
+                        let prev = (index + map.capacity() - 1) & (map.capacity() - 1);
+                        assert!(map.slots[prev].is_some());
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn test_clear_and_iter() {
+            let mut map: OpenHashMap<i32, i32> = OpenHashMap::new(10);
+            map.insert(1, 2);
+            map.insert(3, 4);
+            map.insert(5, 6);
+
+            let mut keys: Vec<_> = map.keys().collect();
+            keys.sort();
+            assert_eq!(keys, vec![&1, &3, &5]);
+            let mut values: Vec<_> = map.values().collect();
+            values.sort();
+            assert_eq!(values, vec![&2, &4, &6]);
+            let mut pairs: Vec<_> = map.iter().collect();
+            pairs.sort_by_key(|&(key, _)| *key);
+            assert_eq!(pairs, vec![(&1, &2), (&3, &4), (&5, &6)]);
+
+            map.clear();
+            assert!(map.is_empty());
+            assert_eq!(map.size(), 0);
+            assert_eq!(map.iter().count(), 0);
+        }
+
+        #[test]
+        fn test_string_keys() {
+            let mut map: OpenHashMap<String, usize> = OpenHashMap::new(4);
+            for word in "a rose is a rose is a rose".split(' ') {
+                let count = map.get(&word.to_string()).copied().unwrap_or(0);
+                map.insert(word.to_string(), count + 1);
+            }
+
+            assert_eq!(map.len(), 3);
+            assert_eq!(map.get(&"rose".to_string()), Some(&3));
+            assert_eq!(map.get(&"a".to_string()), Some(&3));
+            assert_eq!(map.get(&"is".to_string()), Some(&2));
+        }
+
+        #[test]
+        fn test_custom_hasher() {
+            let mut map = OpenHashMap::with_hasher(FxBuildHasher::default());
+            for i in 0..1_000u64 {
+                map.insert(i, i + 1);
+            }
+            for i in 0..1_000u64 {
+                assert_eq!(map.search(&i), Some(&(i + 1)));
+            }
+            assert_eq!(map.search(&1_000), None);
+        }
+
+        #[test]
+        fn test_borrowed_lookups() {
+            let mut map: OpenHashMap<String, i32> = OpenHashMap::new(0);
+            map.insert("one".to_string(), 1);
+            map.insert("two".to_string(), 2);
+
+            assert_eq!(map.get("one"), Some(&1));
+            assert!(map.contains_key("two"));
+            *map.get_mut("two").unwrap() = 22;
+            assert_eq!(map.remove("two"), Some(22));
+            assert!(!map.contains_key("two"));
+        }
+    }
+}
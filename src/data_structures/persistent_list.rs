@@ -0,0 +1,290 @@
+pub mod persistent_list {
+    use std::{fmt, rc::Rc, sync::Arc};
+
+    // Both lists are written once and stamped out for Rc and for Arc:
+    // they only differ in the pointer that shares the nodes.
+    macro_rules! persistent_list {
+        ($list:ident, $ptr:ident) => {
+            // A singly linked, immutable list whose nodes are shared between lists.
+            // cons builds a new list in front of an existing one without copying it,
+            // so keeping every previous version around costs one node per version.
+            pub struct $list<T> {
+                head: Option<$ptr<Node<T>>>,
+            }
+
+            struct Node<T> {
+                value: T,
+                next: Option<$ptr<Node<T>>>,
+            }
+
+            impl<T> $list<T> {
+                pub fn new() -> Self {
+                    $list { head: None }
+                }
+
+                // returns a new list with value in front of this one, which is left unchanged
+                pub fn cons(&self, value: T) -> Self {
+                    $list {
+                        head: Some($ptr::new(Node {
+                            value,
+                            next: self.head.clone(),
+                        })),
+                    }
+                }
+
+                // returns the first element, or None if the list is empty
+                pub fn head(&self) -> Option<&T> {
+                    self.head.as_ref().map(|node| &node.value)
+                }
+
+                // returns the list without its first element, sharing its nodes
+                // the tail of an empty list is empty
+                pub fn tail(&self) -> Self {
+                    $list {
+                        head: self.head.as_ref().and_then(|node| node.next.clone()),
+                    }
+                }
+
+                pub fn is_empty(&self) -> bool {
+                    self.head.is_none()
+                }
+
+                // iterates over the elements from the front
+                pub fn iter(&self) -> Iter<'_, T> {
+                    Iter {
+                        next: self.head.as_deref(),
+                    }
+                }
+
+                // returns true if both lists share the same first node, so they are
+                // the same list without comparing elements
+                pub fn ptr_eq(&self, other: &Self) -> bool {
+                    match (&self.head, &other.head) {
+                        (Some(a), Some(b)) => $ptr::ptr_eq(a, b),
+                        (None, None) => true,
+                        _ => false,
+                    }
+                }
+            }
+
+            // cloning only copies the pointer to the first node
+            impl<T> Clone for $list<T> {
+                fn clone(&self) -> Self {
+                    $list {
+                        head: self.head.clone(),
+                    }
+                }
+            }
+
+            impl<T> Default for $list<T> {
+                fn default() -> Self {
+                    Self::new()
+                }
+            }
+
+            // The default drop would recurse once per node and overflow the stack on long lists.
+            // Instead the nodes are unlinked in a loop, stopping at the first one that is
+            // still shared with another list, since that one and the rest stay alive.
+            // into_inner rather than try_unwrap: when two threads drop lists sharing a node,
+            // both try_unwrap calls can fail and the last Arc dropped would free the rest recursively.
+            impl<T> Drop for $list<T> {
+                fn drop(&mut self) {
+                    let mut current = self.head.take();
+                    while let Some(mut node) = current.and_then($ptr::into_inner) {
+                        current = node.next.take();
+                    }
+                }
+            }
+
+            pub struct Iter<'a, T> {
+                next: Option<&'a Node<T>>,
+            }
+
+            impl<'a, T> Iterator for Iter<'a, T> {
+                type Item = &'a T;
+
+                fn next(&mut self) -> Option<&'a T> {
+                    self.next.map(|node| {
+                        self.next = node.next.as_deref();
+                        &node.value
+                    })
+                }
+            }
+
+            impl<'a, T> IntoIterator for &'a $list<T> {
+                type Item = &'a T;
+                type IntoIter = Iter<'a, T>;
+
+                fn into_iter(self) -> Iter<'a, T> {
+                    self.iter()
+                }
+            }
+
+            // the list keeps the order of the iterator, so the first item becomes the head
+            impl<T> FromIterator<T> for $list<T> {
+                fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+                    let values: Vec<T> = iter.into_iter().collect();
+                    let mut list = $list::new();
+                    for value in values.into_iter().rev() {
+                        list = list.cons(value);
+                    }
+                    list
+                }
+            }
+
+            impl<T: PartialEq> PartialEq for $list<T> {
+                fn eq(&self, other: &Self) -> bool {
+                    self.iter().eq(other)
+                }
+            }
+
+            impl<T: Eq> Eq for $list<T> {}
+
+            impl<T: fmt::Debug> fmt::Debug for $list<T> {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    f.debug_list().entries(self).finish()
+                }
+            }
+        };
+    }
+
+    // the single-threaded list, sharing nodes through Rc
+    pub mod rc {
+        use super::*;
+
+        persistent_list!(List, Rc);
+    }
+
+    // the thread-safe list, sharing nodes through Arc so versions can be sent between threads
+    pub mod arc {
+        use super::*;
+
+        persistent_list!(List, Arc);
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use std::{cell::Cell, thread};
+
+        // long enough to overflow the stack with a recursive drop; Miri checks the
+        // same code paths on a shorter list, since it runs far slower
+        const LONG: i32 = if cfg!(miri) { 1_000 } else { 1_000_000 };
+
+        #[test]
+        fn test_new_list() {
+            let list = rc::List::<i32>::new();
+            assert!(list.is_empty());
+            assert_eq!(list.head(), None);
+            assert!(list.tail().is_empty());
+        }
+
+        #[test]
+        fn test_cons_head_tail() {
+            let list = rc::List::new().cons(3).cons(2).cons(1);
+            assert_eq!(list.head(), Some(&1));
+            assert_eq!(list.tail().head(), Some(&2));
+            assert_eq!(list.tail().tail().head(), Some(&3));
+            assert!(list.tail().tail().tail().is_empty());
+            assert_eq!(list.iter().copied().collect::<Vec<_>>(), [1, 2, 3]);
+        }
+
+        #[test]
+        fn test_structural_sharing() {
+            let base: rc::List<i32> = (1..=3).collect();
+            let a = base.cons(10);
+            let b = base.cons(20);
+
+            // both versions share the nodes of base and base itself is unchanged
+            assert!(a.tail().ptr_eq(&base));
+            assert!(b.tail().ptr_eq(&base));
+            assert!(!a.ptr_eq(&b));
+            assert_eq!(format!("{:?}", base), "[1, 2, 3]");
+            assert_eq!(format!("{:?}", a), "[10, 1, 2, 3]");
+            assert_eq!(format!("{:?}", b), "[20, 1, 2, 3]");
+
+            // dropping a version leaves the shared nodes to the others
+            drop(base);
+            drop(a);
+            assert_eq!(b.iter().sum::<i32>(), 26);
+            assert_eq!(b.tail(), (1..=3).collect());
+        }
+
+        #[test]
+        fn test_drop_frees_each_node_once() {
+            struct DropCounter<'a>(&'a Cell<usize>);
+            impl Drop for DropCounter<'_> {
+                fn drop(&mut self) {
+                    self.0.set(self.0.get() + 1);
+                }
+            }
+
+            let drops = Cell::new(0);
+            let shared = rc::List::new()
+                .cons(DropCounter(&drops))
+                .cons(DropCounter(&drops));
+            let longer = shared.cons(DropCounter(&drops));
+            drop(longer);
+            assert_eq!(drops.get(), 1);
+            drop(shared);
+            assert_eq!(drops.get(), 3);
+        }
+
+        #[test]
+        fn test_long_list_drop_does_not_overflow() {
+            let mut list = rc::List::new();
+            for i in 0..LONG {
+                list = list.cons(i);
+            }
+            let tail = list.tail();
+            drop(list);
+            drop(tail);
+
+            let list: arc::List<i32> = (0..LONG).collect();
+            drop(list);
+        }
+
+        #[test]
+        fn test_concurrent_drops_of_a_long_arc_list() {
+            use std::sync::Barrier;
+
+            const THREADS: usize = 8;
+
+            let list: arc::List<i32> = (0..LONG).collect();
+            let barrier = Arc::new(Barrier::new(THREADS));
+            let handles: Vec<_> = (0..THREADS)
+                .map(|_| {
+                    let list = list.clone();
+                    let barrier = barrier.clone();
+                    thread::spawn(move || {
+                        barrier.wait();
+                        drop(list);
+                    })
+                })
+                .collect();
+            // the threads hold the only references, and race to free the nodes
+            drop(list);
+            for handle in handles {
+                handle.join().unwrap();
+            }
+        }
+
+        #[test]
+        fn test_arc_list_across_threads() {
+            let base: arc::List<i32> = (0..100).collect();
+            let handles: Vec<_> = (0..4)
+                .map(|i| {
+                    let base = base.clone();
+                    thread::spawn(move || {
+                        let version = base.cons(i);
+                        assert!(version.tail().ptr_eq(&base));
+                        version.iter().sum::<i32>()
+                    })
+                })
+                .collect();
+            let sums: Vec<i32> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+            assert_eq!(sums, [4950, 4951, 4952, 4953]);
+            assert_eq!(base.head(), Some(&0));
+        }
+    }
+}
@@ -1,42 +1,185 @@
 pub mod queue {
+    use std::{
+        alloc::Layout,
+        fmt,
+        mem::MaybeUninit,
+        ops::{Bound, Index, IndexMut, RangeBounds},
+        ptr, slice,
+    };
+
+    use crate::data_structures::vec::vec::{TryPushError, TryReserveError};
 
     // a double-ended queu implemented with a growable ring buffer
     // its elements are not necessarily contiguous in memory
     pub struct Queue<T> {
         head: usize,
         len: usize,
-        buffer: Box<[T]>,
+        // only the len slots starting at head, wrapping around the end, are initialized
+        buffer: Box<[MaybeUninit<T>]>,
+        // None for a queue that grows, otherwise what to do when a bounded queue is full
+        policy: Option<OverflowPolicy>,
+        // elements rejected or overwritten because the queue was full
+        dropped: usize,
+    }
+
+    // what pushing onto a full bounded queue does
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum OverflowPolicy {
+        // the new element is handed back to the caller
+        Reject,
+        // the element at the opposite end is evicted to make room, like a ring log
+        Overwrite,
+        // the caller waits for room, which sync::BlockingQueue does for its producers
+        // nothing else can make room in a plain Queue, so there it hands the element back like Reject
+        Block,
     }
 
-    impl<T: Clone + Default + std::fmt::Display> Queue<T> {
+    impl<T> Queue<T> {
         pub fn new() -> Self {
             Self {
                 head: 0,
                 len: 0,
                 buffer: Box::new([]),
+                policy: None,
+                dropped: 0,
+            }
+        }
+
+        // creates a queue that never holds more than capacity elements
+        // and handles pushes onto a full queue according to policy
+        pub fn bounded(capacity: usize, policy: OverflowPolicy) -> Self {
+            assert!(capacity > 0, "capacity must be at least 1");
+            Self {
+                head: 0,
+                len: 0,
+                buffer: uninit_buffer(capacity).unwrap_or_else(|err| err.handle()),
+                policy: Some(policy),
+                dropped: 0,
+            }
+        }
+
+        // returns the number of elements the queue can hold before it grows or overflows
+        pub fn capacity(&self) -> usize {
+            self.buffer.len()
+        }
+
+        // returns the overflow policy, or None if the queue grows on demand
+        pub fn policy(&self) -> Option<OverflowPolicy> {
+            self.policy
+        }
+
+        // returns how many elements were rejected or overwritten because the queue was full
+        pub fn dropped(&self) -> usize {
+            self.dropped
+        }
+
+        // returns the number of elements in the queue
+        pub fn len(&self) -> usize {
+            self.len
+        }
+
+        // return true if the buffer is full
+        pub fn is_full(&self) -> bool {
+            self.len == self.buffer.len()
+        }
+
+        // return true if the queu is empty
+        pub fn is_empty(&self) -> bool {
+            self.len == 0
+        }
+
+        pub fn front(&self) -> Option<&T> {
+            self.get(0)
+        }
+
+        pub fn front_mut(&mut self) -> Option<&mut T> {
+            self.get_mut(0)
+        }
+
+        pub fn back(&self) -> Option<&T> {
+            self.get(self.len.checked_sub(1)?)
+        }
+
+        pub fn back_mut(&mut self) -> Option<&mut T> {
+            self.get_mut(self.len.checked_sub(1)?)
+        }
+
+        // returns the element at position index counting from the front, or None if out of bounds
+        pub fn get(&self, index: usize) -> Option<&T> {
+            if index < self.len {
+                Some(unsafe { self.buffer[self.physical(index)].assume_init_ref() })
+            } else {
+                None
+            }
+        }
+
+        pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+            if index < self.len {
+                let index = self.physical(index);
+                Some(unsafe { self.buffer[index].assume_init_mut() })
+            } else {
+                None
             }
         }
 
         // appends an element to the back of the queue
-        pub fn push_back(&mut self, value: T) {
+        // if a bounded queue is full, returns the element that did not fit:
+        // the value itself when rejecting, or the overwritten front element
+        pub fn push_back(&mut self, value: T) -> Option<T> {
+            let mut evicted = None;
             if self.is_full() {
-                self.grow();
+                match self.policy {
+                    None => self.grow(),
+                    Some(OverflowPolicy::Reject | OverflowPolicy::Block) => {
+                        self.dropped += 1;
+                        return Some(value);
+                    }
+                    Some(OverflowPolicy::Overwrite) => {
+                        self.dropped += 1;
+                        evicted = self.pop_front();
+                    }
+                }
             }
-            let index = (self.head + self.len) % self.buffer.len();
-            self.buffer[index] = value;
+            let index = self.physical(self.len);
+            self.buffer[index].write(value);
             self.len += 1;
+            evicted
         }
 
-        // appends an element to the back of the queue
-        pub fn push_front(&mut self, value: T) {
-            if self.is_full() {
-                self.grow();
+        // like push_back, but returns an error instead of aborting when the queue can't grow
+        // the element is returned inside the error so the caller can retry or shed it
+        // bounded queues never allocate, so for them this applies the policy like push_back
+        // and returns the rejected or overwritten element
+        pub fn try_push_back(&mut self, value: T) -> Result<Option<T>, TryPushError<T>> {
+            if self.is_full() && self.policy.is_none() {
+                if let Err(error) = self.try_grow() {
+                    return Err(TryPushError { value, error });
+                }
             }
-            if self.len > 0 {
-                self.head = (self.head + self.buffer.len() - 1) % self.buffer.len();
+            Ok(self.push_back(value))
+        }
+
+        // appends an element to the front of the queue
+        // a full bounded queue behaves as in push_back, overwriting the back element
+        pub fn push_front(&mut self, value: T) -> Option<T> {
+            let mut evicted = None;
+            if self.is_full() {
+                match self.policy {
+                    None => self.grow(),
+                    Some(OverflowPolicy::Reject | OverflowPolicy::Block) => {
+                        self.dropped += 1;
+                        return Some(value);
+                    }
+                    Some(OverflowPolicy::Overwrite) => {
+                        self.dropped += 1;
+                        evicted = self.pop_back();
+                    }
+                }
             }
-            self.buffer[self.head] = value;
+            self.head = self.physical(self.buffer.len() - 1);
+            self.buffer[self.head].write(value);
             self.len += 1;
+            evicted
         }
 
         // removes the last element from the queue and returns it or None if it is empty
@@ -44,9 +187,9 @@ pub mod queue {
             if self.is_empty() {
                 None
             } else {
-                let index = (self.head + self.len - 1) % self.buffer.len();
                 self.len -= 1;
-                Some(self.buffer[index].clone())
+                let index = self.physical(self.len);
+                Some(unsafe { self.buffer[index].assume_init_read() })
             }
         }
 
@@ -55,42 +198,892 @@ pub mod queue {
             if self.is_empty() {
                 None
             } else {
-                let value = self.buffer[self.head].clone();
-                self.head = (self.head + 1) % self.buffer.len();
+                let value = unsafe { self.buffer[self.head].assume_init_read() };
+                self.head = self.physical(1);
                 self.len -= 1;
                 Some(value)
             }
         }
 
-        // return true if the buffer is full
-        pub fn is_full(&self) -> bool {
-            self.len == self.buffer.len()
+        // iterates over the elements from front to back
+        pub fn iter(&self) -> impl DoubleEndedIterator<Item = &T> {
+            let (first, second) = self.as_slices();
+            first.iter().chain(second)
         }
 
-        // return true if the queu is empty
-        pub fn is_empty(&self) -> bool {
-            self.len == 0
+        pub fn iter_mut(&mut self) -> impl DoubleEndedIterator<Item = &mut T> {
+            let (first, second) = self.as_mut_slices();
+            first.iter_mut().chain(second)
         }
 
-        fn grow(&mut self) {
-            let new_capacity = self.buffer.len().max(1) * 2;
-            let mut new_buffer = vec![T::default(); new_capacity].into_boxed_slice();
+        // returns the elements in order as two slices: the part from head to the end
+        // of the buffer, and the part that wrapped around to its start
+        pub fn as_slices(&self) -> (&[T], &[T]) {
+            let (first, second) = self.slice_ranges();
+            let (wrapped, rest) = self.buffer.split_at(first.0);
+            let (first, second) = (&rest[..first.1 - first.0], &wrapped[..second]);
+            // both ranges lie within the initialized part of the buffer
+            unsafe {
+                (
+                    slice::from_raw_parts(first.as_ptr().cast(), first.len()),
+                    slice::from_raw_parts(second.as_ptr().cast(), second.len()),
+                )
+            }
+        }
+
+        pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+            let (first, second) = self.slice_ranges();
+            let (wrapped, rest) = self.buffer.split_at_mut(first.0);
+            let (first, second) = (&mut rest[..first.1 - first.0], &mut wrapped[..second]);
+            unsafe {
+                (
+                    slice::from_raw_parts_mut(first.as_mut_ptr().cast(), first.len()),
+                    slice::from_raw_parts_mut(second.as_mut_ptr().cast(), second.len()),
+                )
+            }
+        }
+
+        // rearranges the buffer so the elements are contiguous, and returns them as one slice
+        pub fn make_contiguous(&mut self) -> &mut [T] {
+            if self.head + self.len > self.buffer.len() {
+                self.buffer.rotate_left(self.head);
+                self.head = 0;
+            }
+            self.as_mut_slices().0
+        }
+
+        // moves the first n elements to the back, so the element at n becomes the front
+        pub fn rotate_left(&mut self, n: usize) {
+            assert!(n <= self.len, "index out of bounds");
+            // move whichever side is shorter; after a pop the push never has to grow
+            if n <= self.len - n {
+                for _ in 0..n {
+                    let value = self.pop_front().unwrap();
+                    self.push_back(value);
+                }
+            } else {
+                for _ in 0..self.len - n {
+                    let value = self.pop_back().unwrap();
+                    self.push_front(value);
+                }
+            }
+        }
+
+        // moves the last n elements to the front
+        pub fn rotate_right(&mut self, n: usize) {
+            assert!(n <= self.len, "index out of bounds");
+            self.rotate_left(self.len - n);
+        }
+
+        // removes the elements in range and returns them as an iterator
+        // the elements are taken out eagerly, so dropping the iterator early still removes them all
+        pub fn drain<R: RangeBounds<usize>>(
+            &mut self,
+            range: R,
+        ) -> impl DoubleEndedIterator<Item = T> {
+            let (start, end) = self.range_indices(range);
+            let count = end - start;
+            let drained: Vec<T> = (start..end)
+                .map(|i| unsafe { self.buffer[self.physical(i)].assume_init_read() })
+                .collect();
+
+            // close the gap by shifting whichever side of it is shorter;
+            // swapping with a drained slot just moves the element into it
+            if start < self.len - end {
+                for i in (0..start).rev() {
+                    let (from, to) = (self.physical(i), self.physical(i + count));
+                    self.buffer.swap(from, to);
+                }
+                if count > 0 {
+                    self.head = self.physical(count);
+                }
+            } else {
+                for i in end..self.len {
+                    let (from, to) = (self.physical(i), self.physical(i - count));
+                    self.buffer.swap(from, to);
+                }
+            }
+            self.len -= count;
+            drained.into_iter()
+        }
+
+        // keeps only the elements for which keep returns true, preserving their order
+        pub fn retain<F: FnMut(&T) -> bool>(&mut self, mut keep: F) {
+            let mut kept = 0;
             for i in 0..self.len {
-                new_buffer[i] = self.buffer[i].clone();
+                if keep(&self[i]) {
+                    if i != kept {
+                        let (a, b) = (self.physical(kept), self.physical(i));
+                        self.buffer.swap(a, b);
+                    }
+                    kept += 1;
+                }
+            }
+            self.truncate(kept);
+        }
+
+        // shortens the queue keeping the first len elements and dropping the rest
+        pub fn truncate(&mut self, len: usize) {
+            let old_len = self.len;
+            // shrink first, so a panicking drop leaks the rest instead of dropping twice
+            self.len = self.len.min(len);
+            for i in len..old_len {
+                let index = self.physical(i);
+                unsafe { self.buffer[index].assume_init_drop() };
             }
+        }
+
+        // maps a position counted from the front to an index into the buffer
+        fn physical(&self, index: usize) -> usize {
+            (self.head + index) % self.buffer.len()
+        }
+
+        // the buffer range holding the front part and the length of the wrapped part
+        fn slice_ranges(&self) -> ((usize, usize), usize) {
+            if self.len == 0 {
+                return ((0, 0), 0);
+            }
+            let end = self.head + self.len;
+            if end <= self.buffer.len() {
+                ((self.head, end), 0)
+            } else {
+                ((self.head, self.buffer.len()), end - self.buffer.len())
+            }
+        }
+
+        fn range_indices<R: RangeBounds<usize>>(&self, range: R) -> (usize, usize) {
+            let start = match range.start_bound() {
+                Bound::Included(&start) => start,
+                Bound::Excluded(&start) => start
+                    .checked_add(1)
+                    .expect("attempted to index slice from after maximum usize"),
+                Bound::Unbounded => 0,
+            };
+            let end = match range.end_bound() {
+                Bound::Included(&end) => end
+                    .checked_add(1)
+                    .expect("attempted to index slice up to maximum usize"),
+                Bound::Excluded(&end) => end,
+                Bound::Unbounded => self.len,
+            };
+            if start > end || end > self.len {
+                panic!("index out of bounds");
+            }
+            (start, end)
+        }
+
+        fn grow(&mut self) {
+            if let Err(err) = self.try_grow() {
+                err.handle();
+            }
+        }
+
+        // makes room for at least additional more elements without growing
+        pub fn reserve(&mut self, additional: usize) {
+            if let Err(err) = self.try_reserve(additional) {
+                err.handle();
+            }
+        }
+
+        // like reserve, but returns an error if the capacity overflows or the allocator fails
+        // a bounded queue never grows, so asking it for more than its capacity is an overflow
+        pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+            let needed = self
+                .len
+                .checked_add(additional)
+                .ok_or(TryReserveError::CapacityOverflow)?;
+            if needed <= self.buffer.len() {
+                return Ok(());
+            }
+            if self.policy.is_some() {
+                return Err(TryReserveError::CapacityOverflow);
+            }
+            // at least double, so that pushing one element at a time stays amortized O(1)
+            let doubled = self.buffer.len().max(1).saturating_mul(2);
+            self.try_grow_to(needed.max(doubled))
+        }
+
+        // doubles the capacity
+        fn try_grow(&mut self) -> Result<(), TryReserveError> {
+            let new_capacity = self
+                .buffer
+                .len()
+                .max(1)
+                .checked_mul(2)
+                .ok_or(TryReserveError::CapacityOverflow)?;
+            self.try_grow_to(new_capacity)
+        }
+
+        // moves the elements to the start of a new buffer of new_capacity slots
+        // on failure the queue is left untouched
+        fn try_grow_to(&mut self, new_capacity: usize) -> Result<(), TryReserveError> {
+            let mut new_buffer = uninit_buffer(new_capacity)?;
+            let (first, second) = self.as_slices();
+            unsafe {
+                let dst = new_buffer.as_mut_ptr().cast::<T>();
+                ptr::copy_nonoverlapping(first.as_ptr(), dst, first.len());
+                ptr::copy_nonoverlapping(second.as_ptr(), dst.add(first.len()), second.len());
+            }
+            // the old buffer only holds MaybeUninit slots, so dropping it drops no elements
             self.buffer = new_buffer;
+            self.head = 0;
+            Ok(())
         }
+    }
 
-        pub fn to_string(&self) -> String {
-            let mut result = String::new();
-            for i in 0..self.len {
-                let index = (self.head + i) % self.buffer.len();
-                result.push_str(&self.buffer[index].to_string());
-                if i < self.len - 1 {
-                    result.push_str("]");
+    // allocates a buffer of capacity uninitialized slots
+    fn uninit_buffer<T>(capacity: usize) -> Result<Box<[MaybeUninit<T>]>, TryReserveError> {
+        let layout = Layout::array::<T>(capacity).map_err(|_| TryReserveError::CapacityOverflow)?;
+        let mut buffer = Vec::new();
+        buffer
+            .try_reserve_exact(capacity)
+            .map_err(|_| TryReserveError::AllocError { layout })?;
+        buffer.resize_with(capacity, MaybeUninit::uninit);
+        Ok(buffer.into_boxed_slice())
+    }
+
+    impl<T> Drop for Queue<T> {
+        fn drop(&mut self) {
+            self.truncate(0);
+        }
+    }
+
+    impl<T> Default for Queue<T> {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    // the clone keeps the capacity and overflow policy of the original
+    impl<T: Clone> Clone for Queue<T> {
+        fn clone(&self) -> Self {
+            let mut queue = Queue {
+                head: 0,
+                len: 0,
+                buffer: uninit_buffer(self.buffer.len()).unwrap_or_else(|err| err.handle()),
+                policy: self.policy,
+                dropped: self.dropped,
+            };
+            for value in self.iter() {
+                queue.push_back(value.clone());
+            }
+            queue
+        }
+    }
+
+    impl<T> Index<usize> for Queue<T> {
+        type Output = T;
+
+        fn index(&self, index: usize) -> &T {
+            self.get(index).expect("index out of bounds")
+        }
+    }
+
+    impl<T> IndexMut<usize> for Queue<T> {
+        fn index_mut(&mut self, index: usize) -> &mut T {
+            self.get_mut(index).expect("index out of bounds")
+        }
+    }
+
+    impl<T> Extend<T> for Queue<T> {
+        fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+            for value in iter {
+                self.push_back(value);
+            }
+        }
+    }
+
+    impl<T> FromIterator<T> for Queue<T> {
+        fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+            let mut queue = Queue::new();
+            queue.extend(iter);
+            queue
+        }
+    }
+
+    // formats the elements from front to back, e.g. [1, 2, 3]
+    impl<T: fmt::Display> fmt::Display for Queue<T> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "[")?;
+            for (i, value) in self.iter().enumerate() {
+                if i > 0 {
+                    write!(f, ", ")?;
                 }
+                write!(f, "{}", value)?;
+            }
+            write!(f, "]")
+        }
+    }
+
+    // queues that can be shared between threads
+    pub mod sync {
+        use std::{
+            cell::UnsafeCell,
+            fmt,
+            mem::MaybeUninit,
+            sync::{
+                atomic::{AtomicUsize, Ordering},
+                Arc, Condvar, Mutex, MutexGuard,
+            },
+            time::{Duration, Instant},
+        };
+
+        use super::{OverflowPolicy, Queue};
+
+        // a Queue behind a mutex, with condition variables so that consumers can wait
+        // for elements and, under the Block policy, producers can wait for room
+        // once closed it refuses new elements, and pops return None after the rest are drained
+        pub struct BlockingQueue<T> {
+            state: Mutex<State<T>>,
+            not_empty: Condvar,
+            not_full: Condvar,
+        }
+
+        struct State<T> {
+            queue: Queue<T>,
+            closed: bool,
+        }
+
+        // returned by push_back on a closed queue, handing the value back
+        #[derive(Debug, PartialEq, Eq)]
+        pub struct Closed<T>(pub T);
+
+        // why pop_front_timeout did not return an element
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum PopTimeoutError {
+            Timeout,
+            Closed,
+        }
+
+        impl fmt::Display for PopTimeoutError {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                match self {
+                    PopTimeoutError::Timeout => write!(f, "timed out waiting for an element"),
+                    PopTimeoutError::Closed => write!(f, "queue is closed and empty"),
+                }
+            }
+        }
+
+        impl std::error::Error for PopTimeoutError {}
+
+        impl<T> BlockingQueue<T> {
+            // an unbounded queue, whose pushes never wait
+            pub fn new() -> Self {
+                Self::with_queue(Queue::new())
+            }
+
+            // a queue of at most capacity elements, see Queue::bounded
+            pub fn bounded(capacity: usize, policy: OverflowPolicy) -> Self {
+                Self::with_queue(Queue::bounded(capacity, policy))
+            }
+
+            fn with_queue(queue: Queue<T>) -> Self {
+                Self {
+                    state: Mutex::new(State {
+                        queue,
+                        closed: false,
+                    }),
+                    not_empty: Condvar::new(),
+                    not_full: Condvar::new(),
+                }
+            }
+
+            // appends an element, waiting for room if the queue is full under the Block policy
+            // returns the element displaced by the Reject or Overwrite policies like Queue::push_back
+            pub fn push_back(&self, value: T) -> Result<Option<T>, Closed<T>> {
+                let mut state = self.lock();
+                if state.queue.policy() == Some(OverflowPolicy::Block) {
+                    while state.queue.is_full() && !state.closed {
+                        state = self.not_full.wait(state).unwrap();
+                    }
+                }
+                if state.closed {
+                    return Err(Closed(value));
+                }
+                let displaced = state.queue.push_back(value);
+                self.not_empty.notify_one();
+                Ok(displaced)
+            }
+
+            // removes the front element, waiting until there is one
+            // returns None once the queue is closed and empty
+            pub fn pop_front(&self) -> Option<T> {
+                let mut state = self.lock();
+                while state.queue.is_empty() && !state.closed {
+                    state = self.not_empty.wait(state).unwrap();
+                }
+                self.take_front(&mut state)
+            }
+
+            // like pop_front, but gives up after timeout
+            pub fn pop_front_timeout(&self, timeout: Duration) -> Result<T, PopTimeoutError> {
+                // a timeout too long to represent, like Duration::MAX, means waiting forever
+                let Some(deadline) = Instant::now().checked_add(timeout) else {
+                    return self.pop_front().ok_or(PopTimeoutError::Closed);
+                };
+                let mut state = self.lock();
+                // loop because a wake-up can be spurious or lose the race to another consumer
+                while state.queue.is_empty() && !state.closed {
+                    let now = Instant::now();
+                    if now >= deadline {
+                        return Err(PopTimeoutError::Timeout);
+                    }
+                    state = self
+                        .not_empty
+                        .wait_timeout(state, deadline - now)
+                        .unwrap()
+                        .0;
+                }
+                self.take_front(&mut state).ok_or(PopTimeoutError::Closed)
+            }
+
+            // removes the front element if there is one, without waiting
+            pub fn try_pop_front(&self) -> Option<T> {
+                self.take_front(&mut self.lock())
+            }
+
+            // refuses further pushes and wakes every waiting thread
+            pub fn close(&self) {
+                self.lock().closed = true;
+                self.not_empty.notify_all();
+                self.not_full.notify_all();
+            }
+
+            pub fn is_closed(&self) -> bool {
+                self.lock().closed
+            }
+
+            pub fn len(&self) -> usize {
+                self.lock().queue.len()
+            }
+
+            pub fn is_empty(&self) -> bool {
+                self.lock().queue.is_empty()
+            }
+
+            // returns how many elements the overflow policy rejected or overwrote
+            pub fn dropped(&self) -> usize {
+                self.lock().queue.dropped()
+            }
+
+            fn take_front(&self, state: &mut State<T>) -> Option<T> {
+                let value = state.queue.pop_front()?;
+                self.not_full.notify_one();
+                Some(value)
+            }
+
+            // a thread that panicked while holding the lock cannot leave the queue
+            // half-updated, so a poisoned lock is still safe to use
+            fn lock(&self) -> MutexGuard<'_, State<T>> {
+                self.state
+                    .lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner())
+            }
+        }
+
+        impl<T> Default for BlockingQueue<T> {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        // The lock-free ring shared by a single Producer and a single Consumer.
+        // As in Queue the live elements are the len slots starting at head, but here
+        // each side keeps its own end: the consumer owns head, the producer owns
+        // tail = (head + len) % capacity, and the atomic len is the only thing they share.
+        // The producer publishes a slot by incrementing len after writing it, and the
+        // consumer hands a slot back by decrementing len after reading it.
+        struct Ring<T> {
+            buffer: Box<[UnsafeCell<MaybeUninit<T>>]>,
+            len: AtomicUsize,
+            // a copy of the consumer's head, so the ring can drop what is left in it
+            head: AtomicUsize,
+        }
+
+        unsafe impl<T: Send> Sync for Ring<T> {}
+
+        impl<T> Drop for Ring<T> {
+            fn drop(&mut self) {
+                let head = *self.head.get_mut();
+                for i in 0..*self.len.get_mut() {
+                    let slot = self.buffer[(head + i) % self.buffer.len()].get_mut();
+                    unsafe { slot.assume_init_drop() };
+                }
+            }
+        }
+
+        // the sending half of an spsc ring
+        pub struct Producer<T> {
+            ring: Arc<Ring<T>>,
+            tail: usize,
+        }
+
+        // the receiving half of an spsc ring
+        pub struct Consumer<T> {
+            ring: Arc<Ring<T>>,
+            head: usize,
+        }
+
+        // creates a fixed-capacity single-producer single-consumer ring buffer
+        // neither half ever blocks or takes a lock: push fails when full and pop when empty
+        pub fn spsc<T>(capacity: usize) -> (Producer<T>, Consumer<T>) {
+            assert!(capacity > 0, "capacity must be at least 1");
+            let ring = Arc::new(Ring {
+                buffer: (0..capacity)
+                    .map(|_| UnsafeCell::new(MaybeUninit::uninit()))
+                    .collect(),
+                len: AtomicUsize::new(0),
+                head: AtomicUsize::new(0),
+            });
+            (
+                Producer {
+                    ring: ring.clone(),
+                    tail: 0,
+                },
+                Consumer { ring, head: 0 },
+            )
+        }
+
+        impl<T> Producer<T> {
+            // appends value, or hands it back if the ring is full
+            pub fn push(&mut self, value: T) -> Result<(), T> {
+                let ring = &*self.ring;
+                // acquire so the consumer has finished reading the slot we may reuse
+                if ring.len.load(Ordering::Acquire) == ring.buffer.len() {
+                    return Err(value);
+                }
+                unsafe { (*ring.buffer[self.tail].get()).write(value) };
+                self.tail = (self.tail + 1) % ring.buffer.len();
+                ring.len.fetch_add(1, Ordering::AcqRel);
+                Ok(())
+            }
+
+            pub fn len(&self) -> usize {
+                self.ring.len.load(Ordering::Acquire)
+            }
+
+            pub fn is_empty(&self) -> bool {
+                self.len() == 0
+            }
+
+            pub fn capacity(&self) -> usize {
+                self.ring.buffer.len()
+            }
+        }
+
+        impl<T> Consumer<T> {
+            // removes the oldest element, or returns None if the ring is empty
+            pub fn pop(&mut self) -> Option<T> {
+                let ring = &*self.ring;
+                // acquire so the producer's write to the slot is visible
+                if ring.len.load(Ordering::Acquire) == 0 {
+                    return None;
+                }
+                let value = unsafe { (*ring.buffer[self.head].get()).assume_init_read() };
+                self.head = (self.head + 1) % ring.buffer.len();
+                ring.head.store(self.head, Ordering::Relaxed);
+                ring.len.fetch_sub(1, Ordering::AcqRel);
+                Some(value)
+            }
+
+            pub fn len(&self) -> usize {
+                self.ring.len.load(Ordering::Acquire)
+            }
+
+            pub fn is_empty(&self) -> bool {
+                self.len() == 0
+            }
+
+            pub fn capacity(&self) -> usize {
+                self.ring.buffer.len()
+            }
+        }
+
+        #[cfg(test)]
+        mod tests {
+            use super::*;
+            use std::{cell::Cell, rc::Rc, thread};
+
+            #[test]
+            fn test_blocking_pop_waits_for_push() {
+                let queue = Arc::new(BlockingQueue::new());
+                let consumer = {
+                    let queue = queue.clone();
+                    thread::spawn(move || queue.pop_front())
+                };
+                thread::sleep(Duration::from_millis(20));
+                queue.push_back(7).unwrap();
+                assert_eq!(consumer.join().unwrap(), Some(7));
+            }
+
+            #[test]
+            fn test_pop_front_timeout() {
+                let queue = BlockingQueue::<i32>::new();
+                let start = Instant::now();
+                assert_eq!(
+                    queue.pop_front_timeout(Duration::from_millis(30)),
+                    Err(PopTimeoutError::Timeout)
+                );
+                assert!(start.elapsed() >= Duration::from_millis(30));
+
+                queue.push_back(1).unwrap();
+                assert_eq!(queue.pop_front_timeout(Duration::from_millis(30)), Ok(1));
+                queue.close();
+                assert_eq!(
+                    queue.pop_front_timeout(Duration::from_secs(10)),
+                    Err(PopTimeoutError::Closed)
+                );
+            }
+
+            #[test]
+            fn test_pop_front_timeout_max() {
+                let queue = Arc::new(BlockingQueue::new());
+                queue.push_back(1).unwrap();
+                assert_eq!(queue.pop_front_timeout(Duration::MAX), Ok(1));
+
+                // an empty queue waits for the next push instead of overflowing the deadline
+                let consumer = {
+                    let queue = queue.clone();
+                    thread::spawn(move || queue.pop_front_timeout(Duration::MAX))
+                };
+                thread::sleep(Duration::from_millis(20));
+                queue.push_back(2).unwrap();
+                assert_eq!(consumer.join().unwrap(), Ok(2));
+
+                queue.close();
+                assert_eq!(
+                    queue.pop_front_timeout(Duration::MAX),
+                    Err(PopTimeoutError::Closed)
+                );
+            }
+
+            #[test]
+            fn test_close() {
+                let queue = Arc::new(BlockingQueue::new());
+                let waiters: Vec<_> = (0..4)
+                    .map(|_| {
+                        let queue = queue.clone();
+                        thread::spawn(move || queue.pop_front())
+                    })
+                    .collect();
+                thread::sleep(Duration::from_millis(20));
+                queue.push_back(1).unwrap();
+                queue.close();
+
+                // one waiter gets the element and the others wake up empty-handed
+                let mut results: Vec<Option<i32>> =
+                    waiters.into_iter().map(|w| w.join().unwrap()).collect();
+                results.sort();
+                assert_eq!(results, [None, None, None, Some(1)]);
+
+                assert!(queue.is_closed());
+                assert_eq!(queue.push_back(2), Err(Closed(2)));
+                assert_eq!(queue.pop_front(), None);
+            }
+
+            #[test]
+            fn test_close_drains_remaining_elements() {
+                let queue = BlockingQueue::new();
+                queue.push_back("a").unwrap();
+                queue.push_back("b").unwrap();
+                queue.close();
+                assert_eq!(queue.len(), 2);
+                assert_eq!(queue.pop_front(), Some("a"));
+                assert_eq!(queue.try_pop_front(), Some("b"));
+                assert_eq!(queue.pop_front(), None);
+            }
+
+            #[test]
+            fn test_bounded_block_waits_for_room() {
+                let queue = Arc::new(BlockingQueue::bounded(2, OverflowPolicy::Block));
+                let producer = {
+                    let queue = queue.clone();
+                    thread::spawn(move || {
+                        for i in 0..100 {
+                            queue.push_back(i).unwrap();
+                        }
+                    })
+                };
+                let received: Vec<i32> = (0..100).map(|_| queue.pop_front().unwrap()).collect();
+                producer.join().unwrap();
+                assert_eq!(received, (0..100).collect::<Vec<_>>());
+                assert_eq!(queue.dropped(), 0);
+
+                // closing releases a producer stuck on a full queue
+                queue.push_back(0).unwrap();
+                queue.push_back(1).unwrap();
+                let blocked = {
+                    let queue = queue.clone();
+                    thread::spawn(move || queue.push_back(2))
+                };
+                thread::sleep(Duration::from_millis(20));
+                queue.close();
+                assert_eq!(blocked.join().unwrap(), Err(Closed(2)));
+            }
+
+            #[test]
+            fn test_bounded_overwrite_does_not_block() {
+                let queue = BlockingQueue::bounded(2, OverflowPolicy::Overwrite);
+                for i in 0..5 {
+                    queue.push_back(i).unwrap();
+                }
+                assert_eq!(queue.dropped(), 3);
+                assert_eq!(queue.pop_front(), Some(3));
+            }
+
+            #[test]
+            fn test_worker_pool_jobs() {
+                use std::sync::atomic::AtomicU64;
+
+                type Job = Box<dyn FnOnce() + Send>;
+
+                let jobs: Arc<BlockingQueue<Job>> =
+                    Arc::new(BlockingQueue::bounded(4, OverflowPolicy::Block));
+                let total = Arc::new(AtomicU64::new(0));
+                let workers: Vec<_> = (0..3)
+                    .map(|_| {
+                        let jobs = jobs.clone();
+                        thread::spawn(move || {
+                            while let Some(job) = jobs.pop_front() {
+                                job();
+                            }
+                        })
+                    })
+                    .collect();
+
+                for i in 1..=100 {
+                    let total = total.clone();
+                    let job: Job = Box::new(move || {
+                        total.fetch_add(i, Ordering::Relaxed);
+                    });
+                    assert!(matches!(jobs.push_back(job), Ok(None)));
+                }
+                jobs.close();
+                for worker in workers {
+                    worker.join().unwrap();
+                }
+                assert_eq!(total.load(Ordering::Relaxed), 5050);
+            }
+
+            #[test]
+            fn test_blocking_queue_stress() {
+                const PRODUCERS: u64 = 4;
+                const CONSUMERS: usize = 4;
+                // Miri runs far slower, but still interleaves the threads
+                const PER_PRODUCER: u64 = if cfg!(miri) { 50 } else { 5_000 };
+
+                let queue = Arc::new(BlockingQueue::bounded(16, OverflowPolicy::Block));
+                let consumers: Vec<_> = (0..CONSUMERS)
+                    .map(|_| {
+                        let queue = queue.clone();
+                        thread::spawn(move || {
+                            let (mut sum, mut count) = (0, 0);
+                            while let Some(value) = queue.pop_front() {
+                                sum += value;
+                                count += 1;
+                            }
+                            (sum, count)
+                        })
+                    })
+                    .collect();
+                let producers: Vec<_> = (0..PRODUCERS)
+                    .map(|p| {
+                        let queue = queue.clone();
+                        thread::spawn(move || {
+                            for i in 0..PER_PRODUCER {
+                                queue.push_back(p * PER_PRODUCER + i).unwrap();
+                            }
+                        })
+                    })
+                    .collect();
+
+                for producer in producers {
+                    producer.join().unwrap();
+                }
+                queue.close();
+                let (sum, count) = consumers
+                    .into_iter()
+                    .map(|c| c.join().unwrap())
+                    .fold((0, 0), |(s, c), (sum, count)| (s + sum, c + count));
+
+                let total = PRODUCERS * PER_PRODUCER;
+                assert_eq!(count, total);
+                assert_eq!(sum, total * (total - 1) / 2);
+            }
+
+            #[test]
+            fn test_spsc_push_pop() {
+                let (mut producer, mut consumer) = spsc(3);
+                assert_eq!(producer.capacity(), 3);
+                assert_eq!(consumer.pop(), None);
+
+                for i in 0..3 {
+                    assert_eq!(producer.push(i), Ok(()));
+                }
+                assert_eq!(producer.push(3), Err(3));
+                assert_eq!(consumer.len(), 3);
+
+                assert_eq!(consumer.pop(), Some(0));
+                assert_eq!(producer.push(3), Ok(()));
+                assert_eq!(consumer.pop(), Some(1));
+                assert_eq!(consumer.pop(), Some(2));
+                assert_eq!(consumer.pop(), Some(3));
+                assert!(consumer.is_empty());
+            }
+
+            #[test]
+            fn test_spsc_drops_remaining_elements() {
+                struct DropCounter(Rc<Cell<usize>>);
+                impl Drop for DropCounter {
+                    fn drop(&mut self) {
+                        self.0.set(self.0.get() + 1);
+                    }
+                }
+
+                let drops = Rc::new(Cell::new(0));
+                let (mut producer, mut consumer) = spsc(4);
+                for _ in 0..6 {
+                    // push two and pop one so the contents wrap around
+                    let _ = producer.push(DropCounter(drops.clone()));
+                    let _ = producer.push(DropCounter(drops.clone()));
+                    consumer.pop();
+                }
+                let left = consumer.len();
+                let popped = drops.get();
+                drop(producer);
+                drop(consumer);
+                assert_eq!(drops.get(), popped + left);
+                assert_eq!(drops.get(), 12);
+            }
+
+            #[test]
+            fn test_spsc_stress() {
+                const COUNT: u64 = if cfg!(miri) { 500 } else { 200_000 };
+
+                let (mut producer, mut consumer) = spsc(64);
+                let sender = thread::spawn(move || {
+                    for mut value in 0..COUNT {
+                        while let Err(back) = producer.push(value) {
+                            value = back;
+                            thread::yield_now();
+                        }
+                    }
+                });
+
+                // the values must arrive complete and in order
+                let mut expected = 0;
+                while expected < COUNT {
+                    match consumer.pop() {
+                        Some(value) => {
+                            assert_eq!(value, expected);
+                            expected += 1;
+                        }
+                        None => thread::yield_now(),
+                    }
+                }
+                sender.join().unwrap();
+                assert_eq!(consumer.pop(), None);
             }
-            result
         }
     }
 
@@ -110,40 +1103,345 @@ pub mod queue {
             queue.push_back(5);
             queue.push_back(10);
             assert_eq!(queue.len, 2);
-            assert_eq!(queue.buffer[1], 10);
+            assert_eq!(queue[1], 10);
         }
 
         #[test]
         fn test_push_front_queue() {
             let mut queue = Queue::<i32>::new();
             queue.push_front(4);
-            assert_eq!(queue.buffer[queue.head], 4);
+            assert_eq!(queue[0], 4);
             queue.push_front(8);
-            assert_eq!(queue.buffer[queue.head], 8);
+            assert_eq!(queue[0], 8);
             assert_eq!(queue.len, 2);
         }
 
         #[test]
         fn test_pop_back() {
-            let mut queue = Queue {
-                head: 0,
-                len: 5,
-                buffer: Box::new([1, 2, 3, 4, 5]),
-            };
+            let mut queue: Queue<i32> = (1..=5).collect();
             assert_eq!(queue.pop_back(), Some(5));
             assert_eq!(queue.len, 4);
         }
 
         #[test]
         fn test_pop_front() {
-            let mut queue = Queue {
-                head: 0,
-                len: 5,
-                buffer: Box::new([1, 2, 3, 4, 5]),
-            };
+            let mut queue: Queue<i32> = (1..=5).collect();
             assert_eq!(queue.pop_front(), Some(1));
             assert_eq!(queue.len, 4);
             assert_eq!(queue.head, 1);
         }
+
+        #[test]
+        fn test_grow_keeps_wrapped_order() {
+            let mut queue = Queue::new();
+            queue.push_back(1);
+            queue.push_back(2);
+            queue.pop_front();
+            // 3 wraps around to the start of the buffer, then 4 forces a grow
+            queue.push_back(3);
+            queue.push_back(4);
+            assert_eq!(queue.pop_front(), Some(2));
+            assert_eq!(queue.pop_front(), Some(3));
+            assert_eq!(queue.pop_front(), Some(4));
+            assert_eq!(queue.pop_front(), None);
+        }
+
+        #[test]
+        fn test_try_push_back() {
+            use crate::data_structures::failing_alloc::failing_alloc::with_failing_allocations;
+
+            let mut queue = Queue::new();
+            assert_eq!(queue.try_push_back(1), Ok(None));
+            assert_eq!(queue.try_push_back(2), Ok(None));
+            assert!(queue.is_full());
+
+            let err = with_failing_allocations(|| queue.try_push_back(3)).unwrap_err();
+            assert!(matches!(err.error, TryReserveError::AllocError { .. }));
+            assert_eq!(err.value, 3);
+            assert_eq!(queue.len, 2);
+
+            // once memory is available again the queue grows as usual
+            assert_eq!(queue.try_push_back(3), Ok(None));
+            assert_eq!(queue.pop_front(), Some(1));
+            assert_eq!(queue.pop_back(), Some(3));
+        }
+
+        #[test]
+        fn test_try_reserve() {
+            use crate::data_structures::failing_alloc::failing_alloc::with_failing_allocations;
+
+            let mut queue: Queue<u64> = Queue::new();
+            queue.push_back(2);
+            queue.push_front(1);
+            let capacity = queue.capacity();
+            let err = with_failing_allocations(|| queue.try_reserve(100)).unwrap_err();
+            assert!(matches!(err, TryReserveError::AllocError { .. }));
+            assert_eq!(err.to_string(), "failed to allocate 816 bytes");
+            // a failed grow leaves the queue as it was
+            assert_eq!(queue.capacity(), capacity);
+            assert_eq!(queue.to_string(), "[1, 2]");
+
+            assert_eq!(
+                queue.try_reserve(usize::MAX),
+                Err(TryReserveError::CapacityOverflow)
+            );
+            // more than isize::MAX bytes
+            assert_eq!(
+                queue.try_reserve(usize::MAX / 8),
+                Err(TryReserveError::CapacityOverflow)
+            );
+
+            assert_eq!(queue.try_reserve(100), Ok(()));
+            assert!(queue.capacity() >= 102);
+            assert_eq!(queue.to_string(), "[1, 2]");
+            // pushing within the reserved capacity never allocates
+            with_failing_allocations(|| {
+                for i in 3..=102 {
+                    assert_eq!(queue.try_push_back(i), Ok(None));
+                }
+            });
+            assert_eq!(queue.len(), 102);
+        }
+
+        #[test]
+        fn test_try_reserve_bounded() {
+            let mut queue = Queue::bounded(4, OverflowPolicy::Reject);
+            queue.push_back(1);
+            assert_eq!(queue.try_reserve(3), Ok(()));
+            // a bounded queue never grows past its capacity
+            assert_eq!(queue.try_reserve(4), Err(TryReserveError::CapacityOverflow));
+            assert_eq!(queue.capacity(), 4);
+        }
+
+        // builds a full queue of capacity 8 whose contents wrap around the end of the buffer
+        fn wrapped() -> Queue<i32> {
+            let mut queue: Queue<i32> = (0..8).collect();
+            for i in 8..13 {
+                queue.pop_front();
+                queue.push_back(i);
+            }
+            assert_eq!(queue.head, 5);
+            queue
+        }
+
+        #[test]
+        fn test_accessors() {
+            let mut queue = wrapped();
+            assert_eq!(queue.len(), 8);
+            assert_eq!(queue.front(), Some(&5));
+            assert_eq!(queue.back(), Some(&12));
+            assert_eq!(queue.get(3), Some(&8));
+            assert_eq!(queue.get(8), None);
+            assert_eq!(queue[7], 12);
+
+            *queue.front_mut().unwrap() = 50;
+            *queue.back_mut().unwrap() += 100;
+            queue[1] = 60;
+            assert_eq!(queue.to_string(), "[50, 60, 7, 8, 9, 10, 11, 112]");
+
+            let empty = Queue::<i32>::new();
+            assert_eq!(empty.front(), None);
+            assert_eq!(empty.back(), None);
+            assert_eq!(empty.to_string(), "[]");
+        }
+
+        #[test]
+        fn test_iter_and_slices() {
+            let mut queue = wrapped();
+            assert_eq!(queue.as_slices(), (&[5, 6, 7][..], &[8, 9, 10, 11, 12][..]));
+            assert_eq!(
+                queue.iter().rev().copied().collect::<Vec<_>>(),
+                [12, 11, 10, 9, 8, 7, 6, 5]
+            );
+
+            for value in queue.iter_mut() {
+                *value *= 2;
+            }
+            assert_eq!(queue.make_contiguous(), [10, 12, 14, 16, 18, 20, 22, 24]);
+            assert_eq!(queue.as_slices().1, &[] as &[i32]);
+            assert_eq!(queue.front(), Some(&10));
+        }
+
+        #[test]
+        #[should_panic(expected = "index out of bounds")]
+        fn test_index_out_of_bounds() {
+            let queue: Queue<i32> = (0..3).collect();
+            let _ = queue[3];
+        }
+
+        #[test]
+        fn test_rotate() {
+            let mut queue = wrapped();
+            queue.rotate_left(2);
+            assert_eq!(queue.to_string(), "[7, 8, 9, 10, 11, 12, 5, 6]");
+            queue.rotate_left(7);
+            assert_eq!(queue.to_string(), "[6, 7, 8, 9, 10, 11, 12, 5]");
+            queue.rotate_right(3);
+            assert_eq!(queue.to_string(), "[11, 12, 5, 6, 7, 8, 9, 10]");
+            queue.rotate_right(0);
+            assert_eq!(queue.front(), Some(&11));
+        }
+
+        #[test]
+        fn test_drain() {
+            let mut queue = wrapped();
+            let drained: Vec<i32> = queue.drain(1..3).collect();
+            assert_eq!(drained, [6, 7]);
+            assert_eq!(queue.to_string(), "[5, 8, 9, 10, 11, 12]");
+
+            let drained: Vec<i32> = queue.drain(3..).rev().collect();
+            assert_eq!(drained, [12, 11, 10]);
+            assert_eq!(queue.to_string(), "[5, 8, 9]");
+
+            // dropping the iterator without consuming it still removes the range
+            drop(queue.drain(..1));
+            assert_eq!(queue.to_string(), "[8, 9]");
+            queue.push_back(10);
+            queue.push_front(7);
+            assert_eq!(queue.to_string(), "[7, 8, 9, 10]");
+            assert_eq!(queue.drain(..).count(), 4);
+            assert!(queue.is_empty());
+        }
+
+        #[test]
+        fn test_retain_and_truncate() {
+            let mut queue = wrapped();
+            queue.retain(|x| x % 2 == 0);
+            assert_eq!(queue.to_string(), "[6, 8, 10, 12]");
+            queue.push_back(14);
+            assert_eq!(queue.back(), Some(&14));
+
+            queue.truncate(2);
+            assert_eq!(queue.to_string(), "[6, 8]");
+            queue.truncate(5);
+            assert_eq!(queue.len(), 2);
+
+            let mut words: Queue<String> =
+                ["a", "bb", "ccc"].iter().map(|w| w.to_string()).collect();
+            words.retain(|w| w.len() != 2);
+            assert_eq!(words.to_string(), "[a, ccc]");
+        }
+
+        #[test]
+        fn test_elements_need_no_clone_or_default() {
+            use std::{cell::Cell, rc::Rc};
+
+            struct DropCounter(usize, Rc<Cell<usize>>);
+            impl Drop for DropCounter {
+                fn drop(&mut self) {
+                    self.1.set(self.1.get() + 1);
+                }
+            }
+
+            let drops = Rc::new(Cell::new(0));
+            let mut queue = Queue::new();
+            for i in 0..10 {
+                queue.push_back(DropCounter(i, drops.clone()));
+            }
+            // growing moves the elements instead of copying and dropping them
+            assert_eq!(drops.get(), 0);
+
+            assert_eq!(queue.pop_front().map(|d| d.0), Some(0));
+            let drained: Vec<usize> = queue.drain(2..4).map(|d| d.0).collect();
+            assert_eq!(drained, [3, 4]);
+            queue.retain(|d| d.0 != 7);
+            assert_eq!(
+                queue.iter().map(|d| d.0).collect::<Vec<_>>(),
+                [1, 2, 5, 6, 8, 9]
+            );
+            assert_eq!(drops.get(), 4);
+
+            queue.truncate(4);
+            assert_eq!(drops.get(), 6);
+            drop(queue);
+            assert_eq!(drops.get(), 10);
+        }
+
+        #[test]
+        fn test_clone_keeps_policy() {
+            let mut queue = Queue::bounded(3, OverflowPolicy::Overwrite);
+            queue.extend(["a".to_string(), "b".to_string()]);
+            let mut copy = queue.clone();
+            copy.extend(["c".to_string(), "d".to_string()]);
+            assert_eq!(copy.to_string(), "[b, c, d]");
+            assert_eq!(copy.capacity(), 3);
+            assert_eq!(queue.to_string(), "[a, b]");
+        }
+
+        #[test]
+        fn test_bounded_reject() {
+            let mut queue = Queue::bounded(3, OverflowPolicy::Reject);
+            assert_eq!(queue.capacity(), 3);
+            assert_eq!(queue.policy(), Some(OverflowPolicy::Reject));
+            for i in 1..=3 {
+                assert_eq!(queue.push_back(i), None);
+            }
+            assert_eq!(queue.push_back(4), Some(4));
+            assert_eq!(queue.push_front(0), Some(0));
+            assert_eq!(queue.dropped(), 2);
+            assert_eq!(queue.capacity(), 3);
+            assert_eq!(queue.to_string(), "[1, 2, 3]");
+
+            queue.pop_front();
+            assert_eq!(queue.push_back(4), None);
+            assert_eq!(queue.to_string(), "[2, 3, 4]");
+        }
+
+        #[test]
+        fn test_bounded_overwrite() {
+            let mut log = Queue::bounded(4, OverflowPolicy::Overwrite);
+            log.extend(1..=10);
+            assert_eq!(log.to_string(), "[7, 8, 9, 10]");
+            assert_eq!(log.dropped(), 6);
+            assert_eq!(log.capacity(), 4);
+
+            assert_eq!(log.push_back(11), Some(7));
+            assert_eq!(log.push_front(6), Some(11));
+            assert_eq!(log.to_string(), "[6, 8, 9, 10]");
+            // try_push_back applies the policy too and hands back the overwritten element
+            assert_eq!(log.try_push_back(12), Ok(Some(6)));
+            assert_eq!(log.to_string(), "[8, 9, 10, 12]");
+            assert_eq!(log.dropped(), 9);
+        }
+
+        #[test]
+        fn test_bounded_try_push_back_rejects() {
+            use crate::data_structures::failing_alloc::failing_alloc::with_failing_allocations;
+
+            let mut queue = Queue::bounded(2, OverflowPolicy::Reject);
+            assert_eq!(queue.try_push_back(1), Ok(None));
+            assert_eq!(queue.try_push_back(2), Ok(None));
+            // a full bounded queue never allocates, so it hands the value back itself
+            assert_eq!(
+                with_failing_allocations(|| queue.try_push_back(3)),
+                Ok(Some(3))
+            );
+            assert_eq!(queue.dropped(), 1);
+            assert_eq!(queue.to_string(), "[1, 2]");
+        }
+
+        #[test]
+        fn test_bounded_block_on_single_thread() {
+            let mut queue = Queue::bounded(2, OverflowPolicy::Block);
+            assert_eq!(queue.push_back(1), None);
+            assert_eq!(queue.push_front(0), None);
+            // no other thread can make room, so the element comes back instead of waiting
+            assert_eq!(queue.push_back(2), Some(2));
+            assert_eq!(queue.push_front(-1), Some(-1));
+            assert_eq!(queue.try_push_back(3), Ok(Some(3)));
+            assert_eq!(queue.dropped(), 3);
+            assert_eq!(queue.to_string(), "[0, 1]");
+        }
+
+        #[test]
+        fn test_unbounded_still_grows() {
+            let mut queue = Queue::new();
+            for i in 0..100 {
+                assert_eq!(queue.push_back(i), None);
+            }
+            assert_eq!(queue.policy(), None);
+            assert_eq!(queue.dropped(), 0);
+            assert_eq!(queue.capacity(), 128);
+        }
     }
 }
@@ -0,0 +1,406 @@
+pub mod small_vec {
+    use std::{
+        fmt,
+        mem::MaybeUninit,
+        ops::{Deref, DerefMut},
+        ptr, slice,
+    };
+
+    use crate::data_structures::vec::vec::Vec;
+
+    // a vector that keeps up to N elements inline and only moves them
+    // to a heap allocated Vec once the N+1th element is pushed
+    pub struct SmallVec<T, const N: usize> {
+        data: Data<T, N>,
+    }
+
+    enum Data<T, const N: usize> {
+        // only the first len slots of buf hold live values
+        Inline {
+            buf: [MaybeUninit<T>; N],
+            len: usize,
+        },
+        Heap(Vec<T>),
+    }
+
+    impl<T, const N: usize> SmallVec<T, N> {
+        pub fn new() -> Self {
+            Self {
+                data: Data::Inline {
+                    buf: [const { MaybeUninit::uninit() }; N],
+                    len: 0,
+                },
+            }
+        }
+
+        pub fn push(&mut self, el: T) {
+            if self.len() == N {
+                self.spill();
+            }
+            match &mut self.data {
+                Data::Inline { buf, len } => {
+                    buf[*len].write(el);
+                    *len += 1;
+                }
+                Data::Heap(vec) => vec.push(el),
+            }
+        }
+
+        // removes the last element from the vector and returns it
+        pub fn pop(&mut self) -> Option<T> {
+            match &mut self.data {
+                Data::Inline { buf, len } => {
+                    if *len == 0 {
+                        None
+                    } else {
+                        *len -= 1;
+                        unsafe { Some(buf[*len].assume_init_read()) }
+                    }
+                }
+                Data::Heap(vec) => vec.pop(),
+            }
+        }
+
+        // inserts an element at position index, shifting all elements after it to the right
+        pub fn insert(&mut self, index: usize, item: T) {
+            if index > self.len() {
+                panic!("index out of bounds");
+            }
+            if self.len() == N {
+                self.spill();
+            }
+            match &mut self.data {
+                Data::Inline { buf, len } => {
+                    unsafe {
+                        let slot = buf.as_mut_ptr().add(index);
+                        ptr::copy(slot, slot.add(1), *len - index);
+                        (*slot).write(item);
+                    }
+                    *len += 1;
+                }
+                Data::Heap(vec) => vec.insert(index, item),
+            }
+        }
+
+        // removes and returns the element at position index, shifting all elements after it to the left
+        pub fn remove(&mut self, index: usize) -> T {
+            if index >= self.len() {
+                panic!("index out of bounds");
+            }
+            match &mut self.data {
+                Data::Inline { buf, len } => {
+                    *len -= 1;
+                    unsafe {
+                        let slot = buf.as_mut_ptr().add(index);
+                        let result = (*slot).assume_init_read();
+                        ptr::copy(slot.add(1), slot, *len - index);
+                        result
+                    }
+                }
+                Data::Heap(vec) => vec.remove(index),
+            }
+        }
+
+        // returns a reference to the element at position index, or None if out of bounds
+        pub fn get(&self, index: usize) -> Option<&T> {
+            self.as_slice().get(index)
+        }
+
+        // shortens the vector keeping the first len elements and dropping the rest
+        pub fn truncate(&mut self, new_len: usize) {
+            match &mut self.data {
+                Data::Inline { buf, len } => {
+                    if new_len >= *len {
+                        return;
+                    }
+                    let tail = ptr::slice_from_raw_parts_mut(
+                        buf[new_len..].as_mut_ptr() as *mut T,
+                        *len - new_len,
+                    );
+                    // shrink first so a panicking destructor can't cause a double drop
+                    *len = new_len;
+                    unsafe { ptr::drop_in_place(tail) };
+                }
+                Data::Heap(vec) => vec.truncate(new_len),
+            }
+        }
+
+        // returns true if the vector contains no elements
+        pub fn is_empty(&self) -> bool {
+            self.len() == 0
+        }
+
+        // returns the number of elements in the vector
+        pub fn len(&self) -> usize {
+            match &self.data {
+                Data::Inline { len, .. } => *len,
+                Data::Heap(vec) => vec.len(),
+            }
+        }
+
+        // returns the number of elements the vector can hold without (re)allocating
+        pub fn capacity(&self) -> usize {
+            match &self.data {
+                Data::Inline { .. } => N,
+                Data::Heap(vec) => vec.capacity(),
+            }
+        }
+
+        // returns true once the elements have been moved to the heap
+        pub fn spilled(&self) -> bool {
+            matches!(self.data, Data::Heap(_))
+        }
+
+        // moves the elements back inline if they fit again, freeing the heap allocation
+        pub fn shrink_to_fit(&mut self) {
+            let Data::Heap(vec) = &mut self.data else {
+                return;
+            };
+            if vec.len() > N {
+                return;
+            }
+            let mut buf = [const { MaybeUninit::uninit() }; N];
+            let mut len = 0;
+            for el in vec.drain(..) {
+                buf[len].write(el);
+                len += 1;
+            }
+            self.data = Data::Inline { buf, len };
+        }
+
+        pub fn as_slice(&self) -> &[T] {
+            match &self.data {
+                Data::Inline { buf, len } => unsafe {
+                    slice::from_raw_parts(buf.as_ptr() as *const T, *len)
+                },
+                Data::Heap(vec) => vec.as_slice(),
+            }
+        }
+
+        pub fn as_mut_slice(&mut self) -> &mut [T] {
+            match &mut self.data {
+                Data::Inline { buf, len } => unsafe {
+                    slice::from_raw_parts_mut(buf.as_mut_ptr() as *mut T, *len)
+                },
+                Data::Heap(vec) => vec.as_mut_slice(),
+            }
+        }
+
+        // moves the inline elements into a heap allocation with room to grow
+        fn spill(&mut self) {
+            let Data::Inline { buf, len } = &mut self.data else {
+                return;
+            };
+            let mut vec = Vec::new();
+            vec.reserve((N * 2).max(1));
+            for slot in &buf[..*len] {
+                vec.push(unsafe { slot.assume_init_read() });
+            }
+            // the values now belong to vec, so the inline buffer must forget them
+            *len = 0;
+            self.data = Data::Heap(vec);
+        }
+    }
+
+    impl<T, const N: usize> Drop for SmallVec<T, N> {
+        fn drop(&mut self) {
+            // a spilled vector drops its elements through Vec's own Drop
+            if let Data::Inline { .. } = self.data {
+                unsafe { ptr::drop_in_place(self.as_mut_slice()) };
+            }
+        }
+    }
+
+    impl<T, const N: usize> Default for SmallVec<T, N> {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl<T, const N: usize> Deref for SmallVec<T, N> {
+        type Target = [T];
+
+        fn deref(&self) -> &[T] {
+            self.as_slice()
+        }
+    }
+
+    impl<T, const N: usize> DerefMut for SmallVec<T, N> {
+        fn deref_mut(&mut self) -> &mut [T] {
+            self.as_mut_slice()
+        }
+    }
+
+    impl<T: Clone, const N: usize> Clone for SmallVec<T, N> {
+        fn clone(&self) -> Self {
+            self.iter().cloned().collect()
+        }
+    }
+
+    impl<T: fmt::Debug, const N: usize> fmt::Debug for SmallVec<T, N> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_list().entries(self.iter()).finish()
+        }
+    }
+
+    impl<T: PartialEq, const N: usize> PartialEq for SmallVec<T, N> {
+        fn eq(&self, other: &Self) -> bool {
+            self.as_slice() == other.as_slice()
+        }
+    }
+
+    impl<T: Eq, const N: usize> Eq for SmallVec<T, N> {}
+
+    impl<T: PartialEq, const N: usize, const M: usize> PartialEq<[T; M]> for SmallVec<T, N> {
+        fn eq(&self, other: &[T; M]) -> bool {
+            self.as_slice() == other
+        }
+    }
+
+    impl<T, const N: usize> FromIterator<T> for SmallVec<T, N> {
+        fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+            let mut vec = SmallVec::new();
+            vec.extend(iter);
+            vec
+        }
+    }
+
+    impl<T, const N: usize> Extend<T> for SmallVec<T, N> {
+        fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+            for el in iter {
+                self.push(el);
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use std::{cell::Cell, rc::Rc};
+
+        struct DropCounter(Rc<Cell<usize>>);
+
+        impl Drop for DropCounter {
+            fn drop(&mut self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+
+        #[test]
+        fn test_push_pop_inline() {
+            let mut vec: SmallVec<i32, 4> = SmallVec::new();
+            assert!(vec.is_empty());
+            assert_eq!(vec.capacity(), 4);
+
+            vec.push(1);
+            vec.push(2);
+            vec.push(3);
+            assert!(!vec.spilled());
+            assert_eq!(vec.len(), 3);
+            assert_eq!(vec.get(1), Some(&2));
+            assert_eq!(vec.get(3), None);
+
+            assert_eq!(vec.pop(), Some(3));
+            assert_eq!(vec.pop(), Some(2));
+            assert_eq!(vec.pop(), Some(1));
+            assert_eq!(vec.pop(), None);
+        }
+
+        #[test]
+        fn test_spill_to_heap_and_back() {
+            let mut vec: SmallVec<String, 2> = SmallVec::new();
+            vec.push("a".to_string());
+            vec.push("b".to_string());
+            assert!(!vec.spilled());
+
+            vec.push("c".to_string());
+            assert!(vec.spilled());
+            assert!(vec.capacity() >= 3);
+            assert_eq!(vec, ["a", "b", "c"].map(String::from));
+
+            // shrinking is a no-op while the elements don't fit inline
+            vec.shrink_to_fit();
+            assert!(vec.spilled());
+
+            assert_eq!(vec.pop().as_deref(), Some("c"));
+            vec.shrink_to_fit();
+            assert!(!vec.spilled());
+            assert_eq!(vec.capacity(), 2);
+            assert_eq!(vec, ["a", "b"].map(String::from));
+
+            // and it can spill again
+            vec.insert(0, "z".to_string());
+            assert!(vec.spilled());
+            assert_eq!(vec, ["z", "a", "b"].map(String::from));
+        }
+
+        #[test]
+        fn test_insert_remove() {
+            let mut vec: SmallVec<i32, 4> = SmallVec::new();
+            vec.insert(0, 2);
+            vec.insert(0, 0);
+            vec.insert(1, 1);
+            vec.insert(3, 3);
+            assert!(!vec.spilled());
+            assert_eq!(vec, [0, 1, 2, 3]);
+
+            vec.insert(2, 9);
+            assert!(vec.spilled());
+            assert_eq!(vec, [0, 1, 9, 2, 3]);
+
+            assert_eq!(vec.remove(2), 9);
+            vec.shrink_to_fit();
+            assert_eq!(vec.remove(0), 0);
+            assert_eq!(vec.remove(2), 3);
+            assert_eq!(vec, [1, 2]);
+        }
+
+        #[test]
+        #[should_panic(expected = "index out of bounds")]
+        fn test_remove_out_of_bounds() {
+            let mut vec: SmallVec<i32, 4> = SmallVec::new();
+            vec.push(1);
+            vec.remove(1);
+        }
+
+        #[test]
+        fn test_truncate_and_drop() {
+            let drops = Rc::new(Cell::new(0));
+            let mut inline: SmallVec<DropCounter, 8> = SmallVec::new();
+            let mut spilled: SmallVec<DropCounter, 2> = SmallVec::new();
+            for _ in 0..5 {
+                inline.push(DropCounter(drops.clone()));
+                spilled.push(DropCounter(drops.clone()));
+            }
+
+            inline.truncate(2);
+            spilled.truncate(3);
+            assert_eq!(drops.get(), 5);
+            assert_eq!(inline.len(), 2);
+            assert_eq!(spilled.len(), 3);
+
+            // moving back inline must not drop anything
+            spilled.truncate(2);
+            spilled.shrink_to_fit();
+            assert_eq!(drops.get(), 6);
+
+            drop(inline);
+            drop(spilled);
+            assert_eq!(drops.get(), 10);
+        }
+
+        #[test]
+        fn test_slice_methods_and_traits() {
+            let mut vec: SmallVec<i32, 3> = [5, 1, 4, 2].into_iter().collect();
+            vec.sort();
+            assert_eq!(vec, [1, 2, 4, 5]);
+            assert_eq!(vec.iter().sum::<i32>(), 12);
+            assert_eq!(format!("{:?}", vec.clone()), "[1, 2, 4, 5]");
+
+            let mut zero: SmallVec<i32, 0> = SmallVec::new();
+            zero.push(7);
+            assert!(zero.spilled());
+            assert_eq!(zero, [7]);
+        }
+    }
+}
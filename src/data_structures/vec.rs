@@ -1,34 +1,117 @@
 pub mod vec {
     use core::panic;
+    use std::{
+        alloc::{self, Layout},
+        fmt,
+        marker::PhantomData,
+        mem::{self, ManuallyDrop},
+        ops::{Bound, Deref, DerefMut, Index, IndexMut, RangeBounds},
+        ptr::{self, NonNull},
+        slice::{self, SliceIndex},
+    };
 
+    // a growable array that owns a raw, partly uninitialized allocation:
+    // only the first len slots of the buffer hold live values
     pub struct Vec<T> {
-        buffer: Box<[T]>,
+        ptr: NonNull<T>,
+        cap: usize,
         len: usize,
+        // tells the drop checker that we own values of type T
+        _marker: PhantomData<T>,
     }
 
-    impl<T: Clone + Default> Vec<T> {
+    unsafe impl<T: Send> Send for Vec<T> {}
+    unsafe impl<T: Sync> Sync for Vec<T> {}
+
+    impl<T> Vec<T> {
         pub fn new() -> Self {
+            // zero-sized types never need an allocation, so their capacity is unlimited
+            let cap = if mem::size_of::<T>() == 0 {
+                usize::MAX
+            } else {
+                0
+            };
             Self {
-                buffer: Box::new([]),
+                ptr: NonNull::dangling(),
+                cap,
                 len: 0,
+                _marker: PhantomData,
             }
         }
 
         pub fn push(&mut self, el: T) {
-            if self.len == self.buffer.len() {
+            if self.len == self.cap {
                 self.grow();
             }
-            self.buffer[self.len] = el;
+            unsafe {
+                self.ptr.as_ptr().add(self.len).write(el);
+            }
             self.len += 1;
         }
 
+        // like push, but hands back an error instead of aborting when the vector can't grow
+        // the element is returned inside the error so the caller can retry or shed it
+        pub fn try_push(&mut self, el: T) -> Result<(), TryPushError<T>> {
+            if self.len == self.cap {
+                if let Err(error) = self.try_reserve(1) {
+                    return Err(TryPushError { value: el, error });
+                }
+            }
+            self.push(el);
+            Ok(())
+        }
+
+        // doubles the capacity, moving the existing elements into the new allocation
         fn grow(&mut self) {
-            let new_capacity = self.buffer.len().max(1) * 2;
-            let mut new_buffer = vec![T::default(); new_capacity].into_boxed_slice();
-            for i in 0..self.len {
-                new_buffer[i] = self.buffer[i].clone();
+            if let Err(err) = self.try_reserve(1) {
+                err.handle();
+            }
+        }
+
+        // makes room for at least additional more elements without reallocating
+        pub fn reserve(&mut self, additional: usize) {
+            if let Err(err) = self.try_reserve(additional) {
+                err.handle();
+            }
+        }
+
+        // like reserve, but returns an error if the capacity overflows or the allocator fails
+        pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+            let needed = self
+                .len
+                .checked_add(additional)
+                .ok_or(TryReserveError::CapacityOverflow)?;
+            if needed <= self.cap {
+                return Ok(());
             }
-            self.buffer = new_buffer;
+            // at least double, so that pushing one element at a time stays amortized O(1)
+            let doubled = self.cap.max(1).saturating_mul(2);
+            self.try_grow_to(needed.max(doubled))
+        }
+
+        fn try_grow_to(&mut self, new_cap: usize) -> Result<(), TryReserveError> {
+            // only reachable for zero-sized types once len hits usize::MAX
+            if mem::size_of::<T>() == 0 {
+                return Err(TryReserveError::CapacityOverflow);
+            }
+
+            let new_layout =
+                Layout::array::<T>(new_cap).map_err(|_| TryReserveError::CapacityOverflow)?;
+
+            let new_ptr = if self.cap == 0 {
+                unsafe { alloc::alloc(new_layout) }
+            } else {
+                let old_layout = Layout::array::<T>(self.cap).unwrap();
+                unsafe {
+                    alloc::realloc(self.ptr.as_ptr() as *mut u8, old_layout, new_layout.size())
+                }
+            };
+
+            // on failure the old allocation is left untouched
+            self.ptr = NonNull::new(new_ptr as *mut T)
+                .ok_or(TryReserveError::AllocError { layout: new_layout })?;
+            self.cap = new_cap;
+            Ok(())
         }
 
         // removes the last element from a vector and return it
@@ -37,7 +120,7 @@ pub mod vec {
                 None
             } else {
                 self.len -= 1;
-                Some(self.buffer[self.len].clone())
+                unsafe { Some(self.ptr.as_ptr().add(self.len).read()) }
             }
         }
 
@@ -46,13 +129,14 @@ pub mod vec {
             if index > self.len {
                 panic!("index out of bounds");
             }
-            if self.len == self.buffer.len() {
+            if self.len == self.cap {
                 self.grow();
             }
-            for i in (index + 1..self.len + 1).rev() {
-                self.buffer[i] = self.buffer[i - 1].clone();
+            unsafe {
+                let slot = self.ptr.as_ptr().add(index);
+                ptr::copy(slot, slot.add(1), self.len - index);
+                slot.write(item);
             }
-            self.buffer[index] = item;
             self.len += 1;
         }
 
@@ -61,12 +145,13 @@ pub mod vec {
             if index >= self.len {
                 panic!("index out of bounds");
             }
-            let result = self.buffer[index].clone();
-            for i in index..self.len - 1 {
-                self.buffer[i] = self.buffer[i + 1].clone();
-            }
             self.len -= 1;
-            result
+            unsafe {
+                let slot = self.ptr.as_ptr().add(index);
+                let result = slot.read();
+                ptr::copy(slot.add(1), slot, self.len - index);
+                result
+            }
         }
 
         // returns a reference to the element at position index within the vector, or Non e if out of bounds
@@ -74,7 +159,7 @@ pub mod vec {
             if index >= self.len {
                 None
             } else {
-                Some(&self.buffer[index])
+                unsafe { Some(&*self.ptr.as_ptr().add(index)) }
             }
         }
 
@@ -83,17 +168,613 @@ pub mod vec {
             self.len == 0
         }
 
+        // returns the number of elements in the vector
+        pub fn len(&self) -> usize {
+            self.len
+        }
+
+        // returns the number of elements the vector can hold without reallocating
+        pub fn capacity(&self) -> usize {
+            self.cap
+        }
+
+        pub fn as_slice(&self) -> &[T] {
+            unsafe { slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+        }
+
+        pub fn as_mut_slice(&mut self) -> &mut [T] {
+            unsafe { slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+        }
+
+        // removes the element at index and returns it, replacing it with the last element
+        // this does not preserve ordering but is O(1)
+        pub fn swap_remove(&mut self, index: usize) -> T {
+            if index >= self.len {
+                panic!("index out of bounds");
+            }
+            self.len -= 1;
+            unsafe {
+                let base = self.ptr.as_ptr();
+                let result = base.add(index).read();
+                ptr::copy(base.add(self.len), base.add(index), 1);
+                result
+            }
+        }
+
+        // keeps only the elements for which keep returns true, in a single pass
+        pub fn retain<F: FnMut(&T) -> bool>(&mut self, mut keep: F) {
+            self.retain_mut(|el| keep(el));
+        }
+
+        // like retain, but keep may also modify the elements it sees
+        pub fn retain_mut<F: FnMut(&mut T) -> bool>(&mut self, mut keep: F) {
+            let len = self.len;
+            // if keep panics the remaining elements are leaked instead of dropped twice
+            self.len = 0;
+            let mut deleted = 0;
+            unsafe {
+                let base = self.ptr.as_ptr();
+                for i in 0..len {
+                    let current = base.add(i);
+                    if !keep(&mut *current) {
+                        ptr::drop_in_place(current);
+                        deleted += 1;
+                    } else if deleted > 0 {
+                        ptr::copy_nonoverlapping(current, base.add(i - deleted), 1);
+                    }
+                }
+            }
+            self.len = len - deleted;
+        }
+
+        // removes consecutive elements for which same_bucket(current, previous_kept) is true
+        pub fn dedup_by<F: FnMut(&mut T, &mut T) -> bool>(&mut self, mut same_bucket: F) {
+            let len = self.len;
+            if len <= 1 {
+                return;
+            }
+            // if same_bucket panics the remaining elements are leaked instead of dropped twice
+            self.len = 0;
+            let mut write = 1;
+            unsafe {
+                let base = self.ptr.as_ptr();
+                for read in 1..len {
+                    let current = base.add(read);
+                    if same_bucket(&mut *current, &mut *base.add(write - 1)) {
+                        ptr::drop_in_place(current);
+                    } else {
+                        if read != write {
+                            ptr::copy_nonoverlapping(current, base.add(write), 1);
+                        }
+                        write += 1;
+                    }
+                }
+            }
+            self.len = write;
+        }
+
+        // removes consecutive elements that map to the same key
+        pub fn dedup_by_key<K: PartialEq, F: FnMut(&mut T) -> K>(&mut self, mut key: F) {
+            self.dedup_by(|a, b| key(a) == key(b));
+        }
+
+        // removes consecutive repeated elements
+        pub fn dedup(&mut self)
+        where
+            T: PartialEq,
+        {
+            self.dedup_by(|a, b| a == b);
+        }
+
+        // splits the vector in two at index at, returning the elements from at onwards
+        pub fn split_off(&mut self, at: usize) -> Vec<T> {
+            if at > self.len {
+                panic!("index out of bounds");
+            }
+            let count = self.len - at;
+            let mut other = Vec::new();
+            other.reserve(count);
+            unsafe {
+                ptr::copy_nonoverlapping(self.ptr.as_ptr().add(at), other.ptr.as_ptr(), count);
+            }
+            self.len = at;
+            other.len = count;
+            other
+        }
+
+        // moves every element of other to the end of this vector, leaving other empty
+        pub fn append(&mut self, other: &mut Vec<T>) {
+            self.reserve(other.len);
+            unsafe {
+                ptr::copy_nonoverlapping(
+                    other.ptr.as_ptr(),
+                    self.ptr.as_ptr().add(self.len),
+                    other.len,
+                );
+            }
+            self.len += other.len;
+            other.len = 0;
+        }
+
+        // clones and appends every element of the slice
+        pub fn extend_from_slice(&mut self, other: &[T])
+        where
+            T: Clone,
+        {
+            self.reserve(other.len());
+            for el in other {
+                self.push(el.clone());
+            }
+        }
+
+        // grows or shrinks the vector to new_len, filling new slots with clones of value
+        pub fn resize(&mut self, new_len: usize, value: T)
+        where
+            T: Clone,
+        {
+            if new_len <= self.len {
+                self.truncate(new_len);
+                return;
+            }
+            self.reserve(new_len - self.len);
+            while self.len + 1 < new_len {
+                self.push(value.clone());
+            }
+            self.push(value);
+        }
+
+        // removes the elements in range and returns them as an iterator
+        // the tail is shifted back once, when the iterator is dropped
+        pub fn drain<R: RangeBounds<usize>>(&mut self, range: R) -> Drain<'_, T> {
+            let (start, end) = self.range_indices(range);
+            let len = self.len;
+            // until the drain is dropped the vector only owns the elements before the range,
+            // so leaking the drain leaks the rest instead of exposing moved-out slots
+            self.len = start;
+            Drain {
+                vec: self,
+                front: start,
+                back: end,
+                tail_start: end,
+                tail_len: len - end,
+            }
+        }
+
+        // replaces the elements in range with the ones from replace_with
+        // returns the removed elements as an iterator; the replacement happens when it is dropped
+        pub fn splice<R, I>(&mut self, range: R, replace_with: I) -> Splice<'_, I::IntoIter>
+        where
+            R: RangeBounds<usize>,
+            I: IntoIterator<Item = T>,
+        {
+            Splice {
+                drain: self.drain(range),
+                replace_with: replace_with.into_iter(),
+            }
+        }
+
+        fn range_indices<R: RangeBounds<usize>>(&self, range: R) -> (usize, usize) {
+            let start = match range.start_bound() {
+                Bound::Included(&start) => start,
+                Bound::Excluded(&start) => start
+                    .checked_add(1)
+                    .expect("attempted to index slice from after maximum usize"),
+                Bound::Unbounded => 0,
+            };
+            let end = match range.end_bound() {
+                Bound::Included(&end) => end
+                    .checked_add(1)
+                    .expect("attempted to index slice up to maximum usize"),
+                Bound::Excluded(&end) => end,
+                Bound::Unbounded => self.len,
+            };
+            if start > end || end > self.len {
+                panic!("index out of bounds");
+            }
+            (start, end)
+        }
+
         // shortens the vector keeping the first len elements and dropping the rest
         pub fn truncate(&mut self, len: usize) {
             if len < self.len {
+                let tail = ptr::slice_from_raw_parts_mut(
+                    unsafe { self.ptr.as_ptr().add(len) },
+                    self.len - len,
+                );
+                // shrink first so a panicking destructor can't cause a double drop
                 self.len = len;
+                unsafe {
+                    ptr::drop_in_place(tail);
+                }
+            }
+        }
+    }
+
+    impl<T> Drop for Vec<T> {
+        fn drop(&mut self) {
+            self.truncate(0);
+            if self.cap != 0 && mem::size_of::<T>() != 0 {
+                let layout = Layout::array::<T>(self.cap).unwrap();
+                unsafe {
+                    alloc::dealloc(self.ptr.as_ptr() as *mut u8, layout);
+                }
+            }
+        }
+    }
+
+    impl<T> Default for Vec<T> {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    // the error returned by the fallible allocation methods
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum TryReserveError {
+        // the requested capacity does not fit in a usize or exceeds isize::MAX bytes
+        CapacityOverflow,
+        // the allocator could not provide a block with this layout
+        AllocError { layout: Layout },
+    }
+
+    impl TryReserveError {
+        // the infallible methods panic on overflow and abort on allocation failure
+        pub(crate) fn handle(self) -> ! {
+            match self {
+                TryReserveError::CapacityOverflow => panic!("capacity overflow"),
+                TryReserveError::AllocError { layout } => alloc::handle_alloc_error(layout),
             }
         }
     }
 
+    impl fmt::Display for TryReserveError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                TryReserveError::CapacityOverflow => write!(f, "capacity overflow"),
+                TryReserveError::AllocError { layout } => {
+                    write!(f, "failed to allocate {} bytes", layout.size())
+                }
+            }
+        }
+    }
+
+    impl std::error::Error for TryReserveError {}
+
+    // the error returned by try_push and the queue's try_push_back:
+    // the element that could not be stored, along with why
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct TryPushError<T> {
+        pub value: T,
+        pub error: TryReserveError,
+    }
+
+    impl<T> TryPushError<T> {
+        // gives back the element that was not pushed
+        pub fn into_inner(self) -> T {
+            self.value
+        }
+    }
+
+    impl<T> fmt::Display for TryPushError<T> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "could not push the element: {}", self.error)
+        }
+    }
+
+    impl<T: fmt::Debug> std::error::Error for TryPushError<T> {}
+
+    // an iterator moving the elements of a range out of a vector
+    // front..back are the slots still to be yielded, and the tail_len elements
+    // starting at tail_start are moved back behind the vector's len on drop
+    pub struct Drain<'a, T> {
+        vec: &'a mut Vec<T>,
+        front: usize,
+        back: usize,
+        tail_start: usize,
+        tail_len: usize,
+    }
+
+    impl<T> Iterator for Drain<'_, T> {
+        type Item = T;
+
+        fn next(&mut self) -> Option<T> {
+            if self.front == self.back {
+                None
+            } else {
+                self.front += 1;
+                unsafe { Some(self.vec.ptr.as_ptr().add(self.front - 1).read()) }
+            }
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            let len = self.back - self.front;
+            (len, Some(len))
+        }
+    }
+
+    impl<T> DoubleEndedIterator for Drain<'_, T> {
+        fn next_back(&mut self) -> Option<T> {
+            if self.front == self.back {
+                None
+            } else {
+                self.back -= 1;
+                unsafe { Some(self.vec.ptr.as_ptr().add(self.back).read()) }
+            }
+        }
+    }
+
+    impl<T> ExactSizeIterator for Drain<'_, T> {}
+
+    impl<T> Drop for Drain<'_, T> {
+        fn drop(&mut self) {
+            // drop the drained elements nobody asked for
+            for _ in &mut *self {}
+            let vec = &mut *self.vec;
+            unsafe {
+                let base = vec.ptr.as_ptr();
+                ptr::copy(base.add(self.tail_start), base.add(vec.len), self.tail_len);
+            }
+            vec.len += self.tail_len;
+        }
+    }
+
+    // the iterator returned by Vec::splice
+    pub struct Splice<'a, I: Iterator> {
+        drain: Drain<'a, I::Item>,
+        replace_with: I,
+    }
+
+    impl<I: Iterator> Iterator for Splice<'_, I> {
+        type Item = I::Item;
+
+        fn next(&mut self) -> Option<I::Item> {
+            self.drain.next()
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            self.drain.size_hint()
+        }
+    }
+
+    impl<I: Iterator> DoubleEndedIterator for Splice<'_, I> {
+        fn next_back(&mut self) -> Option<I::Item> {
+            self.drain.next_back()
+        }
+    }
+
+    impl<I: Iterator> Drop for Splice<'_, I> {
+        fn drop(&mut self) {
+            for _ in &mut self.drain {}
+            let mut replacement: Vec<I::Item> = self.replace_with.by_ref().collect();
+
+            // open a gap of exactly replacement.len() slots between the head and the tail,
+            // move the replacement in, and leave nothing for the drain to shift
+            let drain = &mut self.drain;
+            let vec = &mut *drain.vec;
+            let count = replacement.len;
+            vec.reserve(drain.tail_len + count);
+            unsafe {
+                let base = vec.ptr.as_ptr();
+                ptr::copy(
+                    base.add(drain.tail_start),
+                    base.add(vec.len + count),
+                    drain.tail_len,
+                );
+                ptr::copy_nonoverlapping(replacement.ptr.as_ptr(), base.add(vec.len), count);
+            }
+            replacement.len = 0;
+            vec.len += count + drain.tail_len;
+            drain.tail_len = 0;
+        }
+    }
+
+    // derefing to a slice gives the vector every read-only and in-place slice method,
+    // including iter, iter_mut, sort and the crate's own sorting functions
+    impl<T> Deref for Vec<T> {
+        type Target = [T];
+
+        fn deref(&self) -> &[T] {
+            self.as_slice()
+        }
+    }
+
+    impl<T> DerefMut for Vec<T> {
+        fn deref_mut(&mut self) -> &mut [T] {
+            self.as_mut_slice()
+        }
+    }
+
+    impl<T, I: SliceIndex<[T]>> Index<I> for Vec<T> {
+        type Output = I::Output;
+
+        fn index(&self, index: I) -> &I::Output {
+            &self.as_slice()[index]
+        }
+    }
+
+    impl<T, I: SliceIndex<[T]>> IndexMut<I> for Vec<T> {
+        fn index_mut(&mut self, index: I) -> &mut I::Output {
+            &mut self.as_mut_slice()[index]
+        }
+    }
+
+    impl<T: Clone> Clone for Vec<T> {
+        fn clone(&self) -> Self {
+            self.iter().cloned().collect()
+        }
+    }
+
+    impl<T: fmt::Debug> fmt::Debug for Vec<T> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_list().entries(self.iter()).finish()
+        }
+    }
+
+    impl<T: PartialEq> PartialEq for Vec<T> {
+        fn eq(&self, other: &Self) -> bool {
+            self.as_slice() == other.as_slice()
+        }
+    }
+
+    impl<T: Eq> Eq for Vec<T> {}
+
+    impl<T: PartialEq> PartialEq<[T]> for Vec<T> {
+        fn eq(&self, other: &[T]) -> bool {
+            self.as_slice() == other
+        }
+    }
+
+    impl<T: PartialEq, const N: usize> PartialEq<[T; N]> for Vec<T> {
+        fn eq(&self, other: &[T; N]) -> bool {
+            self.as_slice() == other
+        }
+    }
+
+    impl<T> FromIterator<T> for Vec<T> {
+        fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+            let mut vec = Vec::new();
+            vec.extend(iter);
+            vec
+        }
+    }
+
+    impl<T> Extend<T> for Vec<T> {
+        fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+            for el in iter {
+                self.push(el);
+            }
+        }
+    }
+
+    impl<'a, T: Copy + 'a> Extend<&'a T> for Vec<T> {
+        fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+            self.extend(iter.into_iter().copied());
+        }
+    }
+
+    impl<'a, T> IntoIterator for &'a Vec<T> {
+        type Item = &'a T;
+        type IntoIter = slice::Iter<'a, T>;
+
+        fn into_iter(self) -> Self::IntoIter {
+            self.iter()
+        }
+    }
+
+    impl<'a, T> IntoIterator for &'a mut Vec<T> {
+        type Item = &'a mut T;
+        type IntoIter = slice::IterMut<'a, T>;
+
+        fn into_iter(self) -> Self::IntoIter {
+            self.iter_mut()
+        }
+    }
+
+    impl<T> IntoIterator for Vec<T> {
+        type Item = T;
+        type IntoIter = IntoIter<T>;
+
+        fn into_iter(self) -> IntoIter<T> {
+            // the iterator takes over the allocation, so the vector must not free it
+            let vec = ManuallyDrop::new(self);
+            IntoIter {
+                ptr: vec.ptr,
+                cap: vec.cap,
+                start: 0,
+                end: vec.len,
+                _marker: PhantomData,
+            }
+        }
+    }
+
+    // an owning iterator that moves the elements out of a vector's buffer
+    // the slots in start..end still hold live values
+    pub struct IntoIter<T> {
+        ptr: NonNull<T>,
+        cap: usize,
+        start: usize,
+        end: usize,
+        _marker: PhantomData<T>,
+    }
+
+    impl<T> Iterator for IntoIter<T> {
+        type Item = T;
+
+        fn next(&mut self) -> Option<T> {
+            if self.start == self.end {
+                None
+            } else {
+                self.start += 1;
+                unsafe { Some(self.ptr.as_ptr().add(self.start - 1).read()) }
+            }
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            let len = self.end - self.start;
+            (len, Some(len))
+        }
+    }
+
+    impl<T> DoubleEndedIterator for IntoIter<T> {
+        fn next_back(&mut self) -> Option<T> {
+            if self.start == self.end {
+                None
+            } else {
+                self.end -= 1;
+                unsafe { Some(self.ptr.as_ptr().add(self.end).read()) }
+            }
+        }
+    }
+
+    impl<T> ExactSizeIterator for IntoIter<T> {}
+
+    impl<T> Drop for IntoIter<T> {
+        fn drop(&mut self) {
+            // drop whatever was not yielded, then free the buffer
+            for _ in &mut *self {}
+            if self.cap != 0 && mem::size_of::<T>() != 0 {
+                let layout = Layout::array::<T>(self.cap).unwrap();
+                unsafe {
+                    alloc::dealloc(self.ptr.as_ptr() as *mut u8, layout);
+                }
+            }
+        }
+    }
+
+    // builds a crate Vec like std's vec!: vector![], vector![1, 2, 3] or vector![0; n]
+    #[macro_export]
+    macro_rules! vector {
+        () => {
+            $crate::data_structures::vec::vec::Vec::new()
+        };
+        ($elem:expr; $n:expr) => {{
+            let elem = $elem;
+            let mut vec = $crate::data_structures::vec::vec::Vec::new();
+            for _ in 0..$n {
+                vec.push(::std::clone::Clone::clone(&elem));
+            }
+            vec
+        }};
+        ($($x:expr),+ $(,)?) => {{
+            let mut vec = $crate::data_structures::vec::vec::Vec::new();
+            $(vec.push($x);)+
+            vec
+        }};
+    }
+
     #[cfg(test)]
     mod tests {
         use super::*;
+        use std::{cell::Cell, rc::Rc};
+
+        // bumps a shared counter when dropped so tests can check destructors run exactly once
+        struct DropCounter(Rc<Cell<usize>>);
+
+        impl Drop for DropCounter {
+            fn drop(&mut self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
 
         #[test]
         fn test_push_and_grow() {
@@ -102,9 +783,9 @@ pub mod vec {
                 vec.push(i);
             }
             assert_eq!(vec.len, 100);
-            assert_eq!(vec.buffer.len(), 128); // buffer size should be next power of 2
+            assert_eq!(vec.cap, 128); // buffer size should be next power of 2
             for i in 0..100 {
-                assert_eq!(vec.buffer[i], i);
+                assert_eq!(vec.get(i), Some(&i));
             }
         }
 
@@ -123,9 +804,9 @@ pub mod vec {
             vec.insert(1, 2);
             vec.insert(1, 3);
             assert_eq!(vec.len, 3);
-            assert_eq!(vec.buffer[0], 1);
-            assert_eq!(vec.buffer[1], 3);
-            assert_eq!(vec.buffer[2], 2);
+            assert_eq!(vec.get(0), Some(&1));
+            assert_eq!(vec.get(1), Some(&3));
+            assert_eq!(vec.get(2), Some(&2));
         }
 
         #[test]
@@ -143,8 +824,8 @@ pub mod vec {
             vec.push(3);
             assert_eq!(vec.remove(1), 2);
             assert_eq!(vec.len, 2);
-            assert_eq!(vec.buffer[0], 1);
-            assert_eq!(vec.buffer[1], 3);
+            assert_eq!(vec.get(0), Some(&1));
+            assert_eq!(vec.get(1), Some(&3));
         }
 
         #[test]
@@ -164,8 +845,413 @@ pub mod vec {
             vec.truncate(5);
             assert_eq!(vec.len, 5);
             for i in 0..5 {
-                assert_eq!(vec.buffer[i], i);
+                assert_eq!(vec.get(i), Some(&i));
+            }
+        }
+
+        #[test]
+        fn test_non_clone_elements_are_moved() {
+            let mut vec = Vec::new();
+            for i in 0..20 {
+                vec.push(Box::new(i));
+            }
+            vec.insert(0, Box::new(-1));
+            assert_eq!(*vec.remove(5), 4);
+            assert_eq!(vec.pop().map(|b| *b), Some(19));
+            assert_eq!(vec.get(0).map(|b| **b), Some(-1));
+            assert_eq!(vec.len, 19);
+        }
+
+        #[test]
+        fn test_truncate_and_drop_run_destructors() {
+            let drops = Rc::new(Cell::new(0));
+            let mut vec = Vec::new();
+            for _ in 0..10 {
+                vec.push(DropCounter(drops.clone()));
+            }
+
+            vec.truncate(4);
+            assert_eq!(drops.get(), 6);
+            let popped = vec.pop();
+            assert_eq!(drops.get(), 6);
+            drop(popped);
+            assert_eq!(drops.get(), 7);
+            drop(vec);
+            assert_eq!(drops.get(), 10);
+        }
+
+        #[test]
+        fn test_growth_does_not_drop_moved_elements() {
+            let drops = Rc::new(Cell::new(0));
+            let mut vec = Vec::new();
+            for _ in 0..33 {
+                vec.push(DropCounter(drops.clone()));
             }
+            assert_eq!(drops.get(), 0);
+            drop(vec);
+            assert_eq!(drops.get(), 33);
+        }
+
+        #[test]
+        fn test_zero_sized_types() {
+            let drops = Rc::new(Cell::new(0));
+            let mut units = Vec::new();
+            for _ in 0..1_000 {
+                units.push(());
+            }
+            assert_eq!(units.len, 1_000);
+            assert_eq!(units.cap, usize::MAX);
+            assert_eq!(units.pop(), Some(()));
+            units.insert(10, ());
+            units.remove(0);
+            assert_eq!(units.len, 999);
+
+            #[derive(Debug, PartialEq)]
+            struct Zst;
+            let mut zsts = Vec::new();
+            zsts.push(Zst);
+            assert_eq!(zsts.get(0), Some(&Zst));
+
+            // zero-sized values with destructors still get dropped
+            struct ZstCounter<'a>(&'a Rc<Cell<usize>>);
+            impl Drop for ZstCounter<'_> {
+                fn drop(&mut self) {
+                    self.0.set(self.0.get() + 1);
+                }
+            }
+            let mut counters = Vec::new();
+            for _ in 0..5 {
+                counters.push(ZstCounter(&drops));
+            }
+            drop(counters);
+            assert_eq!(drops.get(), 5);
+        }
+
+        #[test]
+        fn test_slice_access() {
+            let mut vec = crate::vector![5, 3, 8, 1];
+            assert_eq!(vec.len(), 4);
+            assert_eq!(vec[0], 5);
+            assert_eq!(&vec[1..3], &[3, 8]);
+            assert_eq!(&vec[..], &[5, 3, 8, 1]);
+            assert!(vec.contains(&8));
+            assert_eq!(vec.first(), Some(&5));
+
+            vec[0] = 6;
+            vec[1..].reverse();
+            assert_eq!(vec, [6, 1, 8, 3]);
+            vec.sort();
+            assert_eq!(vec, [1, 3, 6, 8]);
+        }
+
+        #[test]
+        #[should_panic]
+        fn test_index_out_of_bounds() {
+            let vec = crate::vector![1, 2];
+            let _ = vec[2];
+        }
+
+        #[test]
+        fn test_crate_sorting_functions() {
+            use crate::sorting::heap_sort::heap_sort::heap_sort;
+            use crate::sorting::merge_sort::merge_sort::merge_sort;
+            use crate::sorting::quick_sort::quick_sort::quick_sort;
+
+            let mut vec = crate::vector![9, 4, 7, 1, 3];
+            quick_sort(&mut vec);
+            assert_eq!(vec, [1, 3, 4, 7, 9]);
+
+            let mut vec: Vec<i32> = (0..20).rev().collect();
+            merge_sort(&mut vec);
+            assert_eq!(vec, (0..20).collect::<Vec<_>>());
+
+            let mut vec = crate::vector!['c', 'a', 'b'];
+            heap_sort(&mut vec[..]);
+            assert_eq!(vec, ['a', 'b', 'c']);
+        }
+
+        #[test]
+        fn test_iterators() {
+            let mut vec: Vec<i32> = (1..=4).collect();
+            for x in vec.iter_mut() {
+                *x *= 10;
+            }
+            for x in &mut vec {
+                *x += 1;
+            }
+            assert_eq!((&vec).into_iter().sum::<i32>(), 104);
+            assert_eq!(
+                vec.iter().rev().copied().collect::<Vec<_>>(),
+                [41, 31, 21, 11]
+            );
+
+            let mut owned = vec.into_iter();
+            assert_eq!(owned.len(), 4);
+            assert_eq!(owned.next(), Some(11));
+            assert_eq!(owned.next_back(), Some(41));
+            assert_eq!(owned.collect::<Vec<_>>(), [21, 31]);
+        }
+
+        #[test]
+        fn test_into_iter_drops_remaining() {
+            let drops = Rc::new(Cell::new(0));
+            let mut vec = Vec::new();
+            for _ in 0..6 {
+                vec.push(DropCounter(drops.clone()));
+            }
+
+            let mut iter = vec.into_iter();
+            drop(iter.next());
+            drop(iter.next_back());
+            assert_eq!(drops.get(), 2);
+            drop(iter);
+            assert_eq!(drops.get(), 6);
+
+            let zsts: Vec<()> = crate::vector![(); 3];
+            assert_eq!(zsts.into_iter().count(), 3);
+        }
+
+        #[test]
+        fn test_extend_clone_debug_eq() {
+            let mut vec = crate::vector![1, 2];
+            vec.extend(vec![3, 4]);
+            vec.extend(&[5]);
+            assert_eq!(vec, [1, 2, 3, 4, 5]);
+
+            let copy = vec.clone();
+            assert_eq!(copy, vec);
+            vec.push(6);
+            assert_ne!(copy, vec);
+            assert_eq!(copy.len(), 5);
+
+            assert_eq!(format!("{:?}", copy), "[1, 2, 3, 4, 5]");
+            assert_eq!(
+                crate::vector!["a".to_string(); 2],
+                ["a", "a"].map(String::from)
+            );
+            let empty: Vec<i32> = crate::vector![];
+            assert!(empty.is_empty());
+        }
+
+        #[test]
+        fn test_try_reserve_capacity_overflow() {
+            let mut vec: Vec<u64> = crate::vector![1];
+            assert_eq!(
+                vec.try_reserve(usize::MAX),
+                Err(TryReserveError::CapacityOverflow)
+            );
+            // more than isize::MAX bytes
+            assert_eq!(
+                vec.try_reserve(usize::MAX / 8),
+                Err(TryReserveError::CapacityOverflow)
+            );
+
+            let mut units = Vec::new();
+            units.push(());
+            assert_eq!(
+                units.try_reserve(usize::MAX),
+                Err(TryReserveError::CapacityOverflow)
+            );
+            assert_eq!(vec, [1]);
+        }
+
+        #[test]
+        fn test_try_reserve_and_try_push_alloc_failure() {
+            use crate::data_structures::failing_alloc::failing_alloc::with_failing_allocations;
+
+            let mut vec = crate::vector![1, 2];
+            let cap = vec.capacity();
+            let err = with_failing_allocations(|| vec.try_reserve(100)).unwrap_err();
+            assert!(matches!(err, TryReserveError::AllocError { .. }));
+            assert_eq!(err.to_string(), "failed to allocate 408 bytes");
+
+            vec.truncate(cap);
+            while vec.len() < vec.capacity() {
+                vec.push(0);
+            }
+            let full = vec.clone();
+            let err = with_failing_allocations(|| vec.try_push(3)).unwrap_err();
+            assert!(matches!(err.error, TryReserveError::AllocError { .. }));
+            // the element is handed back rather than dropped
+            assert_eq!(err.into_inner(), 3);
+            // a failed grow leaves the vector and its allocation as they were
+            assert_eq!(vec, full);
+            assert_eq!(vec.capacity(), cap);
+
+            // pushing within capacity never allocates
+            let mut roomy: Vec<i32> = Vec::new();
+            roomy.reserve(4);
+            assert_eq!(with_failing_allocations(|| roomy.try_push(1)), Ok(()));
+            assert_eq!(vec.try_push(3), Ok(()));
+            assert_eq!(vec.try_reserve(100), Ok(()));
+        }
+
+        #[test]
+        fn test_swap_remove() {
+            let mut vec = crate::vector![1, 2, 3, 4];
+            assert_eq!(vec.swap_remove(1), 2);
+            assert_eq!(vec, [1, 4, 3]);
+            assert_eq!(vec.swap_remove(2), 3);
+            assert_eq!(vec, [1, 4]);
+        }
+
+        #[test]
+        fn test_retain() {
+            let mut vec: Vec<i32> = (0..20).collect();
+            vec.retain(|x| x % 3 == 0);
+            assert_eq!(vec, [0, 3, 6, 9, 12, 15, 18]);
+
+            vec.retain_mut(|x| {
+                *x += 1;
+                *x < 10
+            });
+            assert_eq!(vec, [1, 4, 7]);
+
+            let drops = Rc::new(Cell::new(0));
+            let mut counters = Vec::new();
+            for _ in 0..10 {
+                counters.push(DropCounter(drops.clone()));
+            }
+            let mut i = 0;
+            counters.retain(|_| {
+                i += 1;
+                i % 2 == 0
+            });
+            assert_eq!(counters.len(), 5);
+            assert_eq!(drops.get(), 5);
+        }
+
+        #[test]
+        fn test_dedup() {
+            let mut vec = crate::vector![1, 1, 2, 3, 3, 3, 1, 4, 4];
+            vec.dedup();
+            assert_eq!(vec, [1, 2, 3, 1, 4]);
+
+            let mut vec = crate::vector![10, 11, 20, 21, 22, 30];
+            vec.dedup_by_key(|x| *x / 10);
+            assert_eq!(vec, [10, 20, 30]);
+
+            let mut words = crate::vector!["a", "A", "b", "B", "b"];
+            words.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+            assert_eq!(words, ["a", "b"]);
+        }
+
+        #[test]
+        fn test_split_off_and_append() {
+            let mut vec: Vec<String> = (0..6).map(|i| i.to_string()).collect();
+            let mut tail = vec.split_off(4);
+            assert_eq!(vec.len(), 4);
+            assert_eq!(tail, ["4", "5"].map(String::from));
+
+            tail.append(&mut vec);
+            assert!(vec.is_empty());
+            assert_eq!(tail, ["4", "5", "0", "1", "2", "3"].map(String::from));
+            assert!(vec.split_off(0).is_empty());
+        }
+
+        #[test]
+        fn test_extend_from_slice_and_resize() {
+            let mut vec = crate::vector![1];
+            vec.extend_from_slice(&[2, 3]);
+            assert_eq!(vec, [1, 2, 3]);
+
+            vec.resize(5, 0);
+            assert_eq!(vec, [1, 2, 3, 0, 0]);
+            vec.resize(2, 9);
+            assert_eq!(vec, [1, 2]);
+
+            vec.reserve(100);
+            assert!(vec.capacity() >= 102);
+        }
+
+        #[test]
+        fn test_drain() {
+            let mut vec: Vec<i32> = (0..10).collect();
+            let drained: Vec<i32> = vec.drain(2..5).collect();
+            assert_eq!(drained, [2, 3, 4]);
+            assert_eq!(vec, [0, 1, 5, 6, 7, 8, 9]);
+
+            let mut drain = vec.drain(..=1);
+            assert_eq!(drain.next_back(), Some(1));
+            drop(drain);
+            assert_eq!(vec, [5, 6, 7, 8, 9]);
+
+            assert_eq!(vec.drain(3..).rev().collect::<Vec<_>>(), [9, 8]);
+            assert_eq!(vec.drain(..).len(), 3);
+            assert!(vec.is_empty());
+        }
+
+        #[test]
+        fn test_drain_drops_unconsumed_elements() {
+            let drops = Rc::new(Cell::new(0));
+            let mut vec = Vec::new();
+            for _ in 0..8 {
+                vec.push(DropCounter(drops.clone()));
+            }
+
+            let mut drain = vec.drain(1..6);
+            drop(drain.next());
+            drop(drain);
+            assert_eq!(drops.get(), 5);
+            assert_eq!(vec.len(), 3);
+
+            drop(vec);
+            assert_eq!(drops.get(), 8);
+        }
+
+        #[test]
+        fn test_leaked_drain() {
+            // counts drops without owning an allocation, so the leaked elements leak nothing
+            struct Counted<'a>(&'a Cell<usize>);
+
+            impl Drop for Counted<'_> {
+                fn drop(&mut self) {
+                    self.0.set(self.0.get() + 1);
+                }
+            }
+
+            let drops = Cell::new(0);
+            let mut vec = Vec::new();
+            for _ in 0..3 {
+                vec.push(Counted(&drops));
+            }
+
+            // a leaked drain leaks elements but never exposes moved-out slots
+            mem::forget(vec.drain(1..));
+            assert_eq!(vec.len(), 1);
+            drop(vec);
+            assert_eq!(drops.get(), 1);
+        }
+
+        #[test]
+        #[should_panic(expected = "index out of bounds")]
+        fn test_drain_out_of_bounds() {
+            let mut vec = crate::vector![1, 2, 3];
+            vec.drain(2..4);
+        }
+
+        #[test]
+        #[should_panic(expected = "attempted to index slice up to maximum usize")]
+        fn test_drain_inclusive_to_max_usize() {
+            let mut vec = crate::vector![1, 2, 3];
+            vec.drain(1..=usize::MAX);
+        }
+
+        #[test]
+        fn test_splice() {
+            let mut vec = crate::vector![1, 2, 3, 4, 5];
+            let removed: Vec<i32> = vec.splice(1..3, [20, 30, 40, 50]).collect();
+            assert_eq!(removed, [2, 3]);
+            assert_eq!(vec, [1, 20, 30, 40, 50, 4, 5]);
+
+            vec.splice(1..5, [0]);
+            assert_eq!(vec, [1, 0, 4, 5]);
+
+            vec.splice(4.., 6..9);
+            assert_eq!(vec, [1, 0, 4, 5, 6, 7, 8]);
+
+            vec.splice(..2, std::iter::empty());
+            assert_eq!(vec, [4, 5, 6, 7, 8]);
         }
     }
 }
@@ -1,9 +1,21 @@
+// every file wraps its code in a module of the same name
+#![allow(clippy::module_inception)]
+
 pub mod data_structures {
     pub mod b_tree_map;
+    pub mod b_tree_set;
+    pub mod cache;
+    #[cfg(test)]
+    pub mod failing_alloc;
     pub mod hash_map;
     pub mod hash_set;
+    pub mod hasher;
+    pub mod intrusive_list;
     pub mod linked_list;
+    pub mod open_hash_map;
+    pub mod persistent_list;
     pub mod queue;
+    pub mod small_vec;
     pub mod vec;
 }
 
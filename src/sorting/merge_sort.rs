@@ -23,11 +23,11 @@ pub mod merge_sort {
 
         while left < a.len() && right < b.len() {
             if a[left] <= b[right] {
-                ret[index] = a[left].clone();
+                ret[index] = a[left];
                 index += 1;
                 left += 1;
             } else {
-                ret[index] = b[right].clone();
+                ret[index] = b[right];
                 index += 1;
                 right += 1;
             }